@@ -1,13 +1,36 @@
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Answer {
-    result: Result<String, String>,
+    result: Result<String, AnswerError>,
 }
 impl Answer {
-    pub fn get_result(&self) -> Result<String, String> {
+    pub fn get_result(&self) -> Result<String, AnswerError> {
         self.result.clone()
     }
 }
 
+/// Reasons why a solution did not produce an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnswerError {
+    /// The part is not implemented yet
+    NotImplemented,
+    /// The solution did not find an answer
+    NoAnswer,
+    /// The solution failed, e.g. on invalid input
+    Failed(String),
+}
+
+impl std::fmt::Display for AnswerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswerError::NotImplemented => write!(f, "Not implemented"),
+            AnswerError::NoAnswer => write!(f, "No answer"),
+            AnswerError::Failed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for AnswerError {}
+
 impl From<String> for Answer {
     fn from(value: String) -> Self {
         Self { result: Ok(value) }
@@ -25,7 +48,7 @@ impl From<&str> for Answer {
 impl From<()> for Answer {
     fn from(_: ()) -> Self {
         Self {
-            result: Err("No answer".to_string()),
+            result: Err(AnswerError::NotImplemented),
         }
     }
 }
@@ -38,7 +61,7 @@ where
         match value {
             Some(v) => Answer::from(v),
             None => Self {
-                result: Err("No answer".to_string()),
+                result: Err(AnswerError::NoAnswer),
             },
         }
     }
@@ -53,12 +76,20 @@ where
         match value {
             Ok(v) => Answer::from(v),
             Err(e) => Self {
-                result: Err(e.to_string()),
+                result: Err(AnswerError::Failed(e.to_string())),
             },
         }
     }
 }
 
+impl From<crate::ParseError> for Answer {
+    fn from(value: crate::ParseError) -> Self {
+        Self {
+            result: Err(AnswerError::Failed(value.to_string())),
+        }
+    }
+}
+
 macro_rules! from_numeric_to_answer {
     ($type:ty) => {
         impl From<$type> for Answer {
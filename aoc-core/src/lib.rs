@@ -1,11 +1,15 @@
 mod answer;
 pub use answer::*;
+mod parse;
+pub use parse::*;
 mod puzzle;
 pub use puzzle::*;
 mod solution_collection;
 pub use solution_collection::*;
 mod solution_wrapper;
 pub use solution_wrapper::*;
+mod submit;
+pub use submit::*;
 mod timer;
 
 pub mod tools;
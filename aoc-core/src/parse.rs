@@ -0,0 +1,350 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::tools::Grid;
+
+/// Error while parsing the puzzle input, pointing at the position where parsing failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the input, numbered from 1
+    pub line: usize,
+    /// Column of the line in characters, numbered from 1
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Move an error found in a part of the input, e.g. a section, to its line in the whole input
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Types which can be parsed from the puzzle input, see [`Puzzle::parse`](crate::Puzzle::parse)
+///
+/// Implemented for the common shapes of puzzle input:
+/// - `String`: the input as is
+/// - `Vec<T>`: one value per line, parsed with [`FromStr`]
+/// - [`Numbers<T>`]: numbers separated by commas or whitespace, on any number of lines
+/// - [`Grid<usize, T>`]: a rectangular grid of characters, converted with `TryFrom<char>`
+/// - [`Sections<T>`]: sections separated by blank lines, each parsed as `T`
+/// - `(A, B)` and `(A, B, C)`: exactly two or three sections, each parsed as its own type
+///
+/// Sections are split like [`Puzzle::sections`](crate::Puzzle::sections), their parse errors point at the line in
+/// the whole input.
+pub trait FromPuzzle: Sized {
+    fn from_puzzle(input: &str) -> Result<Self, ParseError>;
+}
+
+/// Part of the puzzle input between blank lines, see [`Puzzle::sections`](crate::Puzzle::sections)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// Number of lines of the input before the section
+    pub offset: usize,
+    /// Lines of the section, joined with `\n` and without trailing whitespace
+    pub text: String,
+}
+
+impl Section {
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Parse the section, errors point at the line in the whole input
+    pub fn parse<T: FromPuzzle>(&self) -> Result<T, ParseError> {
+        T::from_puzzle(&self.text).map_err(|e| e.offset_lines(self.offset))
+    }
+
+    /// Line of the input after the section, numbered from 1
+    fn end_line(&self) -> usize {
+        self.offset + self.text.lines().count() + 1
+    }
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl FromPuzzle for String {
+    fn from_puzzle(input: &str) -> Result<Self, ParseError> {
+        Ok(input.to_string())
+    }
+}
+
+impl<T> FromPuzzle for Vec<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn from_puzzle(input: &str) -> Result<Self, ParseError> {
+        (1..)
+            .zip(input.lines())
+            .map(|(line, text)| parse_value(text.trim_end(), line, 1))
+            .collect()
+    }
+}
+
+/// Numbers separated by commas or whitespace, e.g. `3,4,5` or `3 4\n5`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Numbers<T>(pub Vec<T>);
+
+impl<T> FromPuzzle for Numbers<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn from_puzzle(input: &str) -> Result<Self, ParseError> {
+        let mut numbers = vec![];
+        for (line, text) in (1..).zip(input.lines()) {
+            let mut start = None;
+            // A separator is appended, so the last number of the line ends like the others
+            let chars = text.char_indices().chain([(text.len(), ',')]);
+            for (column, (byte, c)) in (1..).zip(chars) {
+                let is_separator = c == ',' || c.is_whitespace();
+                match start {
+                    Some((start_column, start_byte)) if is_separator => {
+                        numbers.push(parse_value(&text[start_byte..byte], line, start_column)?);
+                        start = None;
+                    }
+                    None if !is_separator => start = Some((column, byte)),
+                    _ => {}
+                }
+            }
+        }
+        Ok(Numbers(numbers))
+    }
+}
+
+impl<T> FromPuzzle for Grid<usize, T>
+where
+    T: TryFrom<char>,
+    T::Error: Display,
+{
+    fn from_puzzle(input: &str) -> Result<Self, ParseError> {
+        let mut grid = Grid::default();
+        let mut width = None;
+        for (y, text) in input.lines().enumerate() {
+            let text = text.trim_end_matches('\r');
+            let line_width = text.chars().count();
+            let expected = *width.get_or_insert(line_width);
+            if line_width != expected {
+                return Err(ParseError::new(
+                    y + 1,
+                    line_width.min(expected) + 1,
+                    format!(
+                        "expected a grid {} columns wide, line has {}",
+                        expected, line_width
+                    ),
+                ));
+            }
+            for (x, c) in text.chars().enumerate() {
+                let cell = T::try_from(c).map_err(|e| {
+                    ParseError::new(y + 1, x + 1, format!("invalid cell {:?}: {}", c, e))
+                })?;
+                grid.insert(x, y, cell);
+            }
+        }
+        Ok(grid)
+    }
+}
+
+/// Sections of the input separated by blank lines, each parsed as `T`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sections<T>(pub Vec<T>);
+
+impl<T: FromPuzzle> FromPuzzle for Sections<T> {
+    fn from_puzzle(input: &str) -> Result<Self, ParseError> {
+        split_sections(input)
+            .iter()
+            .map(Section::parse)
+            .collect::<Result<_, _>>()
+            .map(Sections)
+    }
+}
+
+impl<A: FromPuzzle, B: FromPuzzle> FromPuzzle for (A, B) {
+    fn from_puzzle(input: &str) -> Result<Self, ParseError> {
+        let [a, b] = expect_sections(split_sections(input))?;
+        Ok((a.parse()?, b.parse()?))
+    }
+}
+
+impl<A: FromPuzzle, B: FromPuzzle, C: FromPuzzle> FromPuzzle for (A, B, C) {
+    fn from_puzzle(input: &str) -> Result<Self, ParseError> {
+        let [a, b, c] = expect_sections(split_sections(input))?;
+        Ok((a.parse()?, b.parse()?, c.parse()?))
+    }
+}
+
+fn parse_value<T>(text: &str, line: usize, column: usize) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.parse()
+        .map_err(|e| ParseError::new(line, column, format!("invalid value {:?}: {}", text, e)))
+}
+
+/// Split the input on blank lines, normalizing line endings and trailing whitespace
+pub(crate) fn split_sections(input: &str) -> Vec<Section> {
+    let mut sections = vec![];
+    let mut current: Option<(usize, Vec<&str>)> = None;
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            sections.extend(current.take());
+        } else {
+            current.get_or_insert((i, vec![])).1.push(line);
+        }
+    }
+    sections.extend(current);
+    sections
+        .into_iter()
+        .map(|(offset, lines)| Section {
+            offset,
+            text: lines.join("\n"),
+        })
+        .collect()
+}
+
+fn expect_sections<const N: usize>(sections: Vec<Section>) -> Result<[Section; N], ParseError> {
+    let found = sections.len();
+    let line = match (sections.get(N), sections.last()) {
+        (Some(extra), _) => extra.offset + 1,
+        (None, Some(last)) => last.end_line(),
+        (None, None) => 1,
+    };
+    sections.try_into().map_err(|_| {
+        ParseError::new(
+            line,
+            1,
+            format!(
+                "expected {} sections separated by blank lines, found {}",
+                N, found
+            ),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(Vec::<u32>::from_puzzle("1\n2\n3\n"), Ok(vec![1, 2, 3]));
+        let error = Vec::<u32>::from_puzzle("1\nx\n3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: invalid value \"x\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(
+            Numbers::<i32>::from_puzzle("3,4, -5\n6  7"),
+            Ok(Numbers(vec![3, 4, -5, 6, 7]))
+        );
+        let error = Numbers::<u32>::from_puzzle("1 2\n3 4x 5").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::<usize, char>::from_puzzle("ab\ncd\n").unwrap();
+        assert_eq!(grid.get(1, 0), Some(&'b'));
+        assert_eq!(grid.size(), (2, 2));
+
+        let error = Grid::<usize, char>::from_puzzle("abc\nab").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        struct Digit(u32);
+        impl TryFrom<char> for Digit {
+            type Error = &'static str;
+            fn try_from(c: char) -> Result<Self, Self::Error> {
+                c.to_digit(10).map(Digit).ok_or("not a digit")
+            }
+        }
+        let error = Grid::<usize, Digit>::from_puzzle("12\n3x").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: invalid cell 'x': not a digit"
+        );
+        let grid = Grid::<usize, Digit>::from_puzzle("12\n34").ok().unwrap();
+        assert_eq!(grid.values().map(|Digit(d)| d).sum::<u32>(), 10);
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "1-3\n5-6\n\n2\n4\n";
+        let (ranges, ids): (Vec<String>, Vec<u32>) = FromPuzzle::from_puzzle(input).unwrap();
+        assert_eq!(ranges, vec!["1-3", "5-6"]);
+        assert_eq!(ids, vec![2, 4]);
+
+        let error = <(Vec<String>, Vec<u32>)>::from_puzzle("a\n\n1\nx").unwrap_err();
+        assert_eq!(
+            (error.line, error.column),
+            (4, 1),
+            "line in the whole input"
+        );
+        let error = <(String, String)>::from_puzzle("a\nb").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected 2 sections separated by blank lines, found 1"
+        );
+
+        let Sections(sections) = Sections::<Numbers<u32>>::from_puzzle("1 2\n\n\n3\n\n4").unwrap();
+        assert_eq!(
+            sections,
+            vec![Numbers(vec![1, 2]), Numbers(vec![3]), Numbers(vec![4])]
+        );
+    }
+
+    #[test]
+    fn test_puzzle_sections() {
+        let puzzle = crate::Puzzle::from("1-3 \r\n5-6\r\n \r\n2\r\n\r\n\r\n4\r\n");
+        let sections = puzzle.sections();
+        assert_eq!(
+            sections.iter().map(Section::as_str).collect::<Vec<_>>(),
+            vec!["1-3\n5-6", "2", "4"]
+        );
+        assert_eq!(puzzle.section(2).map(|s| s.offset), Some(6));
+        assert_eq!(puzzle.section(3), None);
+
+        let (ranges, ids, last) = puzzle.parse::<(Vec<String>, Vec<u32>, Vec<u32>)>().unwrap();
+        assert_eq!(ranges, vec!["1-3", "5-6"]);
+        assert_eq!((ids, last), (vec![2], vec![4]));
+
+        let Sections(per_line) = puzzle.parse::<Sections<Vec<String>>>().unwrap();
+        assert_eq!(per_line, vec![vec!["1-3", "5-6"], vec!["2"], vec!["4"]]);
+
+        let error = puzzle.parse::<Sections<Numbers<u32>>>().unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        let error = puzzle.parse::<(String, String)>().unwrap_err();
+        assert_eq!(error.line, 7, "line of the third section");
+    }
+}
@@ -1,4 +1,10 @@
-use aoc_procmacro_internals::{get_aoc_data, AocDataType};
+use aoc_procmacro_internals::{get_aoc_data, AocDataType, AocError};
+
+use crate::{
+    parse::split_sections,
+    tools::parse::{ints, Integer, Ints, Minus},
+    FromPuzzle, ParseError, Section,
+};
 
 #[derive(Debug, Clone)]
 pub struct Puzzle {
@@ -6,9 +12,17 @@ pub struct Puzzle {
 }
 
 impl Puzzle {
-    pub(crate) fn new(day: u32, year: u32) -> Self {
-        let input = get_aoc_data(AocDataType::Input, day, year).expect("Failed to get input");
-        Self { input }
+    /// Load the puzzle input, downloading it when it is not cached yet
+    pub fn try_new(day: u32, year: u32) -> Result<Self, AocError> {
+        let input = get_aoc_data(AocDataType::Input, day, year)?;
+        Ok(Self { input })
+    }
+    /// Load an example from the puzzle text, numbered from 1
+    pub fn example(day: u32, year: u32, n: usize) -> Self {
+        match get_aoc_data(AocDataType::Example(n), day, year) {
+            Ok(input) => Self { input },
+            Err(e) => panic!("Failed to get example {} of day {}: {}", n, day, e),
+        }
     }
     pub fn input_as_str(&self) -> &str {
         &self.input
@@ -19,6 +33,33 @@ impl Puzzle {
     pub fn get_input_lines(&self) -> Vec<&str> {
         self.input.lines().collect()
     }
+    /// Parse the input into a type, e.g. `puzzle.parse::<Vec<u32>>()` for a number per line, or
+    /// `puzzle.parse::<(Vec<String>, Vec<u64>)>()` for two sections separated by a blank line
+    ///
+    /// See [`FromPuzzle`] for the supported shapes of input.
+    pub fn parse<T: FromPuzzle>(&self) -> Result<T, ParseError> {
+        T::from_puzzle(&self.input)
+    }
+    /// Sections of the input separated by blank lines
+    ///
+    /// Line endings are normalized to `\n` and trailing whitespace is removed, so CRLF input splits the same.
+    pub fn sections(&self) -> Vec<Section> {
+        split_sections(&self.input)
+    }
+    /// Section of the input, numbered from 0 like [`Puzzle::sections`]
+    pub fn section(&self, n: usize) -> Option<Section> {
+        self.sections().into_iter().nth(n)
+    }
+    /// All integers in the input, e.g. `puzzle.numbers::<u64>()` for `11-22,95-115`
+    ///
+    /// A `-` directly after a digit separates a range, see [`Minus::Range`]; use [`ints`] for other inputs.
+    pub fn numbers<T: Integer>(&self) -> Ints<'_, T> {
+        ints(&self.input, Minus::Range)
+    }
+    /// The integers of each line of the input, like [`Puzzle::numbers`]
+    pub fn line_numbers<T: Integer>(&self) -> impl Iterator<Item = Ints<'_, T>> {
+        self.input.lines().map(|line| ints(line, Minus::Range))
+    }
 }
 
 impl std::fmt::Display for Puzzle {
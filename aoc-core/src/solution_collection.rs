@@ -1,72 +1,286 @@
-use crate::{Answer, RunnableSolution};
-use std::{collections::HashMap, time::Duration};
+use crate::{
+    check_answer, check_cache, encrypt_cache, fetch_answers, refetch, submit_answer, Answer,
+    AnswerLedger, AocError, ParseError, ParseResult, ParsedInput, Puzzle, RunnableSolution,
+    SubmitOutcome,
+};
+use std::{collections::HashMap, hint::black_box, rc::Rc, time::Duration};
 
 #[derive(Default)]
 pub struct SolutionCollection {
     solutions: HashMap<u32, Box<dyn RunnableSolution>>,
 }
 
+/// Options to control how solutions are run
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RunOptions {
+    /// Submit the answers of each part to AOC after running
+    pub submit: bool,
+}
+
 impl SolutionCollection {
     pub fn register_solution(&mut self, solution: Box<dyn RunnableSolution>) {
         self.solutions.insert(solution.get_day(), solution);
     }
 
     pub fn run(&self, day: Option<u32>) {
+        self.run_with_options(day, RunOptions::default());
+    }
+
+    pub fn run_with_options(&self, day: Option<u32>, options: RunOptions) {
         if let Some(day) = day {
-            self.run_day(&day);
+            self.run_day(&day, &options);
         } else {
             let mut days = self.solutions.keys().collect::<Vec<_>>();
             days.sort();
             let mut total_time = Duration::default();
             for day in days {
-                total_time += self.run_day(day);
+                total_time += self.run_day(day, &options);
             }
             println!("total_time: {:.2?}", total_time);
         }
     }
 
-    fn run_day(&self, day: &u32) -> Duration {
+    fn run_day(&self, day: &u32, options: &RunOptions) -> Duration {
         if !self.solutions.contains_key(day) {
             panic!("Day {} was not yet created", day);
         }
-        let solution = &self.solutions.get(day).unwrap();
-        let puzzle = solution.get_puzzle(); // Preload puzzle
+        let solution = self.solutions.get(day).unwrap();
         println!("Day {}", day);
-        let (part1, time1) = crate::timed!(solution.part1(&puzzle));
-        let (part2, time2) = crate::timed!(solution.part2(&puzzle));
-        println!("Part 1: {}", display_answer(part1));
-        println!("Part 2: {}", display_answer(part2));
+        let puzzle = match solution.get_puzzle() {
+            Ok(puzzle) => puzzle, // Preload puzzle
+            Err(e) => {
+                println!("Day {} input unavailable: {}", day, e);
+                return Duration::default();
+            }
+        };
+        let (parsed, parse_time) = crate::timed!(solution.parse(&puzzle));
+        let ([part1, part2], times) = solve(solution.as_ref(), &parsed);
+        println!(
+            "Part 1: {}",
+            display_checked_answer(solution.as_ref(), 1, &part1)
+        );
+        println!(
+            "Part 2: {}",
+            display_checked_answer(solution.as_ref(), 2, &part2)
+        );
+        let times = solution
+            .has_parse_step()
+            .then_some(("parse", parse_time))
+            .into_iter()
+            .chain(times)
+            .collect::<Vec<_>>();
+        let total_time = times.iter().map(|(_, time)| *time).sum::<Duration>();
+        println!(
+            "time: {:.2?} ({})",
+            total_time,
+            times
+                .iter()
+                .map(|(step, time)| format!("{}: {:.2?}", step, time))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        if options.submit {
+            submit_answers(solution.as_ref(), [&part1, &part2]);
+        }
+        total_time
+    }
+
+    /// Run all days and compare the answers with the correct answers stored in the answer ledgers.
+    ///
+    /// With `fetch_answers`, correct answers missing from a ledger are first scraped from the puzzle page.
+    /// Returns false if any answer does not match, or has no correct answer to compare with.
+    pub fn verify(&self, fetch_answers: bool) -> bool {
+        let mut days = self.get_days();
+        days.sort();
+        let (mut passed, mut failed, mut unverified) = (0, 0, 0);
+        println!(
+            "{:>4} {:>4}  {:<10} {:<20} expected",
+            "day", "part", "result", "answer"
+        );
+        for day in days {
+            let solution = self.solutions.get(&day).unwrap();
+            let expected = get_expected_answers(solution.as_ref(), fetch_answers);
+            let puzzle = match solution.get_puzzle() {
+                Ok(puzzle) => puzzle,
+                Err(e) => {
+                    println!("{:>4}       FAIL       input unavailable: {}", day, e);
+                    failed += 2;
+                    continue;
+                }
+            };
+            let (answers, _) = solve(solution.as_ref(), &solution.parse(&puzzle));
+            for ((part, answer), expected) in (1..).zip(answers).zip(expected) {
+                let result = match (answer.get_result(), &expected) {
+                    (_, None) => {
+                        unverified += 1;
+                        "unverified"
+                    }
+                    (Ok(answer), Some(expected)) if &answer == expected => {
+                        passed += 1;
+                        "pass"
+                    }
+                    _ => {
+                        failed += 1;
+                        "FAIL"
+                    }
+                };
+                println!(
+                    "{:>4} {:>4}  {:<10} {:<20} {}",
+                    day,
+                    part,
+                    result,
+                    display_answer(&answer),
+                    expected.as_deref().unwrap_or("-")
+                );
+            }
+        }
         println!(
-            "time: {:.2?} (1: {:.2?}, 2: {:.2?})",
-            time1 + time2,
-            time1,
-            time2
+            "{} passed, {} failed, {} unverified",
+            passed, failed, unverified
         );
-        time1 + time2
+        if unverified > 0 && !fetch_answers {
+            println!("Record the missing answers by submitting them, or pass --fetch-answers to download them");
+        }
+        failed == 0 && unverified == 0
+    }
+
+    /// Check the cached data of all days for error pages and files changed since they were fetched.
+    ///
+    /// Returns false if any suspicious file was found.
+    pub fn check_cache(&self) -> bool {
+        let mut days = self.get_days();
+        days.sort();
+        let mut success = true;
+        for day in days {
+            let solution = self.solutions.get(&day).unwrap();
+            match check_cache(day, solution.get_year()) {
+                Ok(issues) => {
+                    for issue in issues {
+                        println!("Day {} {}: {}", day, issue.file, issue.reason);
+                        success = false;
+                    }
+                }
+                Err(e) => {
+                    println!("Day {} cache unreadable: {}", day, e);
+                    success = false;
+                }
+            }
+        }
+        if success {
+            println!("No suspicious cache entries found");
+        }
+        success
     }
 
-    pub fn run_day_part1(&self, day: &u32) -> (Answer, std::time::Duration) {
+    /// Encrypt the cached input and text of all days with the cache passphrase
+    pub fn encrypt_cache(&self) -> Result<(), AocError> {
+        let mut days = self.get_days();
+        days.sort();
+        for day in days {
+            let solution = self.solutions.get(&day).unwrap();
+            let count = encrypt_cache(day, solution.get_year())?;
+            if count > 0 {
+                println!("Day {}: encrypted {} files", day, count);
+            }
+        }
+        Ok(())
+    }
+
+    /// Download the data of a day again, replacing the cached files
+    pub fn refetch(&self, day: &u32) -> Result<(), AocError> {
+        let Some(solution) = self.solutions.get(day) else {
+            panic!("Day {} was not yet created", day);
+        };
+        refetch(*day, solution.get_year())
+    }
+
+    /// Run part 1 of a day, timing the part without parsing the input
+    ///
+    /// A combined solution has no separate part timings, run it with [`SolutionCollection::run_day_solve`].
+    pub fn run_day_part1(&self, day: &u32) -> Result<(Answer, std::time::Duration), AocError> {
         let solution = &self.solutions.get(day).unwrap();
-        let puzzle = solution.get_puzzle(); // Preload puzzle
-        crate::timed!(solution.part1(&puzzle))
+        ensure_separate_parts(solution.as_ref())?;
+        let puzzle = solution.get_puzzle()?; // Preload puzzle
+        let parsed = solution.parse(&puzzle);
+        let (answer, time) = crate::timed!(with_parsed(&parsed, |parsed| parsed.part1()));
+        Ok((answer.into(), time))
     }
-    pub fn run_day_part2(&self, day: &u32) -> (Answer, std::time::Duration) {
+    /// Run part 2 of a day, timing the part without parsing the input
+    ///
+    /// A combined solution has no separate part timings, run it with [`SolutionCollection::run_day_solve`].
+    pub fn run_day_part2(&self, day: &u32) -> Result<(Answer, std::time::Duration), AocError> {
         let solution = &self.solutions.get(day).unwrap();
-        let puzzle = solution.get_puzzle(); // Preload puzzle
-        crate::timed!(solution.part2(&puzzle))
+        ensure_separate_parts(solution.as_ref())?;
+        let puzzle = solution.get_puzzle()?; // Preload puzzle
+        let parsed = solution.parse(&puzzle);
+        let (answer, time) = crate::timed!(with_parsed(&parsed, |parsed| parsed.part2()));
+        Ok((answer.into(), time))
     }
-
-    pub fn prepare_bench(
+    /// Solve both parts of a day, timing them together without parsing the input
+    pub fn run_day_solve(
         &self,
         day: &u32,
-    ) -> (impl Fn() -> Answer + use<'_>, impl Fn() -> Answer + use<'_>) {
+    ) -> Result<((Answer, Answer), std::time::Duration), AocError> {
+        let solution = &self.solutions.get(day).unwrap();
+        let puzzle = solution.get_puzzle()?; // Preload puzzle
+        let parsed = solution.parse(&puzzle);
+        let (answers, time) = crate::timed!(match &parsed {
+            Ok(parsed) => parsed.solve(),
+            Err(e) => (e.clone().into(), e.clone().into()),
+        });
+        Ok((answers, time))
+    }
+
+    /// Named functions to benchmark a day: the parse step, and `part1` and `part2` or the combined `solve`
+    ///
+    /// The parts share input parsed up front, so their benchmarks leave out the parse step. Solutions without a
+    /// parse step of their own, which parse in each part, have no `parse` function. The parsed input may borrow
+    /// from the puzzle, load it with [`SolutionCollection::get_puzzle`].
+    pub fn prepare_bench<'a>(
+        &'a self,
+        day: &u32,
+        puzzle: &'a Puzzle,
+    ) -> Vec<(&'static str, BenchFunction<'a>)> {
         let solution = self.solutions.get(day).unwrap();
-        let puzzle1 = solution.get_puzzle(); // Preload puzzle
-        let puzzle2 = puzzle1.clone();
-        (
-            move || solution.part1(&puzzle1),
-            move || solution.part2(&puzzle2),
-        )
+        let parsed = Rc::new(solution.parse(puzzle));
+        let combined = solution.is_combined();
+        let mut functions: Vec<(&'static str, BenchFunction<'a>)> = vec![];
+        if solution.has_parse_step() {
+            functions.push((
+                "parse",
+                Box::new(move || {
+                    let _ = black_box(solution.parse(puzzle));
+                }),
+            ));
+        }
+        if combined {
+            functions.push((
+                "solve",
+                Box::new(move || {
+                    let _ = black_box(with_parsed(&parsed, |parsed| parsed.solve()));
+                }),
+            ));
+        } else {
+            let parsed2 = parsed.clone();
+            functions.push((
+                "part1",
+                Box::new(move || {
+                    let _ = black_box(with_parsed(&parsed, |parsed| parsed.part1()));
+                }),
+            ));
+            functions.push((
+                "part2",
+                Box::new(move || {
+                    let _ = black_box(with_parsed(&parsed2, |parsed| parsed.part2()));
+                }),
+            ));
+        }
+        functions
+    }
+
+    /// Load the puzzle input of a day
+    pub fn get_puzzle(&self, day: &u32) -> Result<Puzzle, AocError> {
+        self.solutions.get(day).unwrap().get_puzzle()
     }
 
     pub fn get_days(&self) -> Vec<u32> {
@@ -74,10 +288,105 @@ impl SolutionCollection {
     }
 }
 
-fn display_answer(answer: Answer) -> String {
+/// Function which runs a step of a day, for benchmarks
+pub type BenchFunction<'a> = Box<dyn Fn() + 'a>;
+
+/// Solve both parts on the parsed input, with the time of each step; a parse error fails both parts
+fn solve(
+    solution: &dyn RunnableSolution,
+    parsed: &ParseResult<'_>,
+) -> ([Answer; 2], Vec<(&'static str, Duration)>) {
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return ([e.clone().into(), e.clone().into()], vec![]),
+    };
+    if solution.is_combined() {
+        let ((part1, part2), time) = crate::timed!(parsed.solve());
+        ([part1, part2], vec![("solve", time)])
+    } else {
+        let (part1, time1) = crate::timed!(parsed.part1());
+        let (part2, time2) = crate::timed!(parsed.part2());
+        ([part1, part2], vec![("1", time1), ("2", time2)])
+    }
+}
+
+/// Refuse to time a single part of a combined solution, which always solves both
+fn ensure_separate_parts(solution: &dyn RunnableSolution) -> Result<(), AocError> {
+    if solution.is_combined() {
+        return Err(AocError::NotFound(format!(
+            "day {} solves both parts at once, its parts have no separate timings",
+            solution.get_day()
+        )));
+    }
+    Ok(())
+}
+
+fn with_parsed<T>(
+    parsed: &ParseResult<'_>,
+    step: impl Fn(&dyn ParsedInput) -> T,
+) -> Result<T, ParseError> {
+    match parsed {
+        Ok(parsed) => Ok(step(parsed.as_ref())),
+        Err(e) => Err(e.clone()),
+    }
+}
+
+fn display_answer(answer: &Answer) -> String {
     match answer.get_result() {
         Ok(result) => result,
-        Err(e) => e,
+        Err(e) => e.to_string(),
+    }
+}
+
+/// Correct answers from the answer ledger, with `fetch` missing answers are scraped from the puzzle page
+fn get_expected_answers(solution: &dyn RunnableSolution, fetch: bool) -> [Option<String>; 2] {
+    let (day, year) = (solution.get_day(), solution.get_year());
+    let mut ledger = AnswerLedger::load(day, year).ok();
+    let complete = ledger.as_ref().is_some_and(|ledger| {
+        ledger.correct_answer(1).is_some() && ledger.correct_answer(2).is_some()
+    });
+    if fetch && !complete {
+        match fetch_answers(day, year) {
+            Ok(fetched) => ledger = Some(fetched),
+            Err(e) => println!("{:>4}       answers unavailable: {}", day, e),
+        }
+    }
+    match ledger {
+        Some(ledger) => [1, 2].map(|part| ledger.correct_answer(part).map(String::from)),
+        None => [None, None],
+    }
+}
+
+fn display_checked_answer(solution: &dyn RunnableSolution, part: u8, answer: &Answer) -> String {
+    if answer.get_result().is_err() {
+        return display_answer(answer);
+    }
+    let status = check_answer(solution.get_day(), solution.get_year(), part, answer);
+    format!("{} ({})", display_answer(answer), status)
+}
+
+fn submit_answers(solution: &dyn RunnableSolution, answers: [&Answer; 2]) {
+    for (part, answer) in (1..).zip(answers) {
+        if answer.get_result().is_err() {
+            println!("Part {}: not submitted, no answer", part);
+            continue;
+        }
+        match submit_answer(solution.get_day(), solution.get_year(), part, answer) {
+            Ok(SubmitOutcome::AlreadyVerified) => {
+                println!("Part {}: not submitted, already verified", part);
+            }
+            Ok(outcome) => {
+                println!("Part {} submitted: {}", part, outcome);
+                // Part 2 is locked until part 1 is correct
+                if outcome != SubmitOutcome::Correct {
+                    break;
+                }
+            }
+            Err(e) => {
+                println!("Part {} submission failed: {}", part, e);
+                break;
+            }
+        }
     }
 }
 
@@ -97,6 +406,16 @@ macro_rules! setup_solutions {
             let puzzles = get_collection();
             puzzles.run(day);
         }
+
+        pub fn run_with_options(day: Option<u32>, options: aoc_core::RunOptions) {
+            let puzzles = get_collection();
+            puzzles.run_with_options(day, options);
+        }
+
+        pub fn verify(fetch_answers: bool) -> bool {
+            let puzzles = get_collection();
+            puzzles.verify(fetch_answers)
+        }
     };
 }
 
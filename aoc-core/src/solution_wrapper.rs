@@ -1,21 +1,126 @@
-use crate::{Answer, Puzzle};
+use std::sync::OnceLock;
+
+use crate::{Answer, AocError, ParseError, Puzzle};
 
 pub trait RunnableSolution: Send + Sync {
-    fn get_puzzle(&self) -> Puzzle;
-    fn part1(&self, puzzle: &Puzzle) -> Answer;
-    fn part2(&self, puzzle: &Puzzle) -> Answer;
+    fn get_puzzle(&self) -> Result<Puzzle, AocError>;
+    /// Parse the puzzle once, the parsed input runs both parts
+    fn parse<'a>(&'a self, puzzle: &'a Puzzle) -> ParseResult<'a>;
+    /// Whether the solution solves both parts at once, see [`CombinedPuzzleSolution`]
+    fn is_combined(&self) -> bool;
+    /// Whether the solution has a parse step of its own, a [`PuzzleSolution`] parses in each part
+    fn has_parse_step(&self) -> bool;
     fn get_day(&self) -> u32;
+    fn get_year(&self) -> u32;
 }
 
+/// Puzzle input parsed by a solution, ready to run the parts
+pub trait ParsedInput: Send + Sync {
+    fn part1(&self) -> Answer;
+    fn part2(&self) -> Answer;
+    /// Solve both parts, running the solution on every call
+    fn solve(&self) -> (Answer, Answer);
+}
+
+/// Input parsed by a [`RunnableSolution`], or why parsing failed
+pub type ParseResult<'a> = Result<Box<dyn ParsedInput + 'a>, ParseError>;
+
 pub trait PuzzleSolution: Send + Sync {
     fn part1(&self, puzzle: &Puzzle) -> Answer;
     fn part2(&self, puzzle: &Puzzle) -> Answer;
 }
 
-pub struct SolutionWrapper<S>
+/// Solution which parses the input once, and shares the result between both parts
+///
+/// Every [`PuzzleSolution`] implements this, with a reference to the puzzle as parsed input. The runner and the
+/// benchmarks time the parse step separately from the parts. The parsed input may borrow from the puzzle.
+///
+/// ```no_compile
+/// impl ParsedPuzzleSolution for Day {
+///     type Parsed<'a> = Vec<u32>;
+///
+///     fn parse<'a>(&self, puzzle: &'a Puzzle) -> Result<Self::Parsed<'a>, ParseError> {
+///         puzzle.parse()
+///     }
+///     fn part1(&self, numbers: &Self::Parsed<'_>) -> Answer {
+///         numbers.iter().sum::<u32>().into()
+///     }
+///     fn part2(&self, numbers: &Self::Parsed<'_>) -> Answer {
+///         numbers.iter().max().into()
+///     }
+/// }
+/// ```
+pub trait ParsedPuzzleSolution: Send + Sync {
+    type Parsed<'a>: Send + Sync;
+
+    fn parse<'a>(&self, puzzle: &'a Puzzle) -> Result<Self::Parsed<'a>, ParseError>;
+    fn part1(&self, parsed: &Self::Parsed<'_>) -> Answer;
+    fn part2(&self, parsed: &Self::Parsed<'_>) -> Answer;
+
+    /// Whether [`ParsedPuzzleSolution::parse`] does any work, the runner and benchmarks only time it when it does
+    const PARSE_STEP: bool = true;
+
+    /// Parse the puzzle and run part 1, e.g. in tests
+    fn run_part1(&self, puzzle: &Puzzle) -> Answer {
+        match self.parse(puzzle) {
+            Ok(parsed) => self.part1(&parsed),
+            Err(e) => e.into(),
+        }
+    }
+
+    /// Parse the puzzle and run part 2, e.g. in tests
+    fn run_part2(&self, puzzle: &Puzzle) -> Answer {
+        match self.parse(puzzle) {
+            Ok(parsed) => self.part2(&parsed),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl<S> ParsedPuzzleSolution for S
 where
     S: PuzzleSolution,
 {
+    type Parsed<'a> = &'a Puzzle;
+
+    const PARSE_STEP: bool = false;
+
+    fn parse<'a>(&self, puzzle: &'a Puzzle) -> Result<Self::Parsed<'a>, ParseError> {
+        Ok(puzzle)
+    }
+
+    fn part1(&self, puzzle: &Self::Parsed<'_>) -> Answer {
+        PuzzleSolution::part1(self, puzzle)
+    }
+
+    fn part2(&self, puzzle: &Self::Parsed<'_>) -> Answer {
+        PuzzleSolution::part2(self, puzzle)
+    }
+}
+
+/// Solution which solves both parts at once, so part 2 can reuse intermediate results of part 1
+///
+/// Register it with `#[aoc_puzzle(day = 1, combined)]`, which wraps it in [`Combined`]. The runner times the parse
+/// step and the combined solve, and the benchmarks run them as `parse` and `solve` instead of `part1` and `part2`.
+pub trait CombinedPuzzleSolution: Send + Sync {
+    type Parsed: Send + Sync;
+
+    fn parse(&self, puzzle: &Puzzle) -> Result<Self::Parsed, ParseError>;
+    fn solve(&self, parsed: &Self::Parsed) -> (Answer, Answer);
+
+    /// Parse the puzzle and solve both parts, e.g. in tests
+    fn run(&self, puzzle: &Puzzle) -> (Answer, Answer) {
+        match self.parse(puzzle) {
+            Ok(parsed) => self.solve(&parsed),
+            Err(e) => (e.clone().into(), e.into()),
+        }
+    }
+}
+
+/// Marks a [`CombinedPuzzleSolution`] for the [`SolutionWrapper`], which then solves both parts at once
+pub struct Combined<S>(pub S);
+
+pub struct SolutionWrapper<S> {
     solution: S,
     props: SolutionProps,
 }
@@ -25,10 +130,7 @@ pub struct SolutionProps {
     pub day: u32,
 }
 
-impl<S> SolutionWrapper<S>
-where
-    S: PuzzleSolution,
-{
+impl<S> SolutionWrapper<S> {
     pub fn new(solution: S, props: SolutionProps) -> Self {
         Self { solution, props }
     }
@@ -36,21 +138,128 @@ where
 
 impl<P> RunnableSolution for SolutionWrapper<P>
 where
-    P: PuzzleSolution,
+    P: ParsedPuzzleSolution,
+{
+    fn get_puzzle(&self) -> Result<Puzzle, AocError> {
+        Puzzle::try_new(self.props.day, self.props.year)
+    }
+
+    fn parse<'a>(&'a self, puzzle: &'a Puzzle) -> ParseResult<'a> {
+        let parsed = self.solution.parse(puzzle)?;
+        Ok(Box::new(WrappedInput {
+            solution: &self.solution,
+            parsed,
+        }))
+    }
+
+    fn is_combined(&self) -> bool {
+        false
+    }
+
+    fn has_parse_step(&self) -> bool {
+        P::PARSE_STEP
+    }
+
+    fn get_day(&self) -> u32 {
+        self.props.day
+    }
+
+    fn get_year(&self) -> u32 {
+        self.props.year
+    }
+}
+
+impl<C> RunnableSolution for SolutionWrapper<Combined<C>>
+where
+    C: CombinedPuzzleSolution,
 {
-    fn get_puzzle(&self) -> Puzzle {
-        Puzzle::new(self.props.day, self.props.year)
+    fn get_puzzle(&self) -> Result<Puzzle, AocError> {
+        Puzzle::try_new(self.props.day, self.props.year)
+    }
+
+    fn parse<'a>(&'a self, puzzle: &'a Puzzle) -> ParseResult<'a> {
+        let parsed = self.solution.0.parse(puzzle)?;
+        Ok(Box::new(CombinedInput {
+            solution: &self.solution.0,
+            parsed,
+            answers: OnceLock::new(),
+        }))
     }
 
-    fn part1(&self, puzzle: &Puzzle) -> Answer {
-        self.solution.part1(puzzle)
+    fn is_combined(&self) -> bool {
+        true
     }
 
-    fn part2(&self, puzzle: &Puzzle) -> Answer {
-        self.solution.part2(puzzle)
+    fn has_parse_step(&self) -> bool {
+        true
     }
 
     fn get_day(&self) -> u32 {
         self.props.day
     }
+
+    fn get_year(&self) -> u32 {
+        self.props.year
+    }
+}
+
+struct WrappedInput<'a, S>
+where
+    S: ParsedPuzzleSolution,
+{
+    solution: &'a S,
+    parsed: S::Parsed<'a>,
+}
+
+impl<S> ParsedInput for WrappedInput<'_, S>
+where
+    S: ParsedPuzzleSolution,
+{
+    fn part1(&self) -> Answer {
+        self.solution.part1(&self.parsed)
+    }
+
+    fn part2(&self) -> Answer {
+        self.solution.part2(&self.parsed)
+    }
+
+    fn solve(&self) -> (Answer, Answer) {
+        (self.part1(), self.part2())
+    }
+}
+
+/// Input parsed by a [`CombinedPuzzleSolution`], the first part to run solves both and keeps the other answer
+struct CombinedInput<'a, C>
+where
+    C: CombinedPuzzleSolution,
+{
+    solution: &'a C,
+    parsed: C::Parsed,
+    answers: OnceLock<(Answer, Answer)>,
+}
+
+impl<C> CombinedInput<'_, C>
+where
+    C: CombinedPuzzleSolution,
+{
+    fn answers(&self) -> &(Answer, Answer) {
+        self.answers.get_or_init(|| self.solve())
+    }
+}
+
+impl<C> ParsedInput for CombinedInput<'_, C>
+where
+    C: CombinedPuzzleSolution,
+{
+    fn part1(&self) -> Answer {
+        self.answers().0.clone()
+    }
+
+    fn part2(&self) -> Answer {
+        self.answers().1.clone()
+    }
+
+    fn solve(&self) -> (Answer, Answer) {
+        self.solution.solve(&self.parsed)
+    }
 }
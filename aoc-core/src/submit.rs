@@ -0,0 +1,23 @@
+use crate::Answer;
+use aoc_procmacro_internals::{AnswerLedger, AnswerStatus, AocError, SubmitOutcome};
+
+/// Submit the answer for a part of a puzzle to AOC.
+pub fn submit_answer(
+    day: u32,
+    year: u32,
+    part: u8,
+    answer: &Answer,
+) -> Result<SubmitOutcome, AocError> {
+    let answer = answer
+        .get_result()
+        .map_err(|e| AocError::Refused(e.to_string()))?;
+    aoc_procmacro_internals::submit_answer(day, year, part, &answer)
+}
+
+/// Check the answer for a part of a puzzle against the answers submitted earlier.
+pub fn check_answer(day: u32, year: u32, part: u8, answer: &Answer) -> AnswerStatus {
+    match (answer.get_result(), AnswerLedger::load(day, year)) {
+        (Ok(answer), Ok(ledger)) => ledger.check(part, &answer),
+        _ => AnswerStatus::Unknown,
+    }
+}
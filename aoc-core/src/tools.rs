@@ -4,3 +4,4 @@ mod dijkstra;
 pub use dijkstra::*;
 mod grid;
 pub use grid::*;
+pub mod parse;
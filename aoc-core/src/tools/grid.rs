@@ -61,7 +61,7 @@ where
     pub fn contains_key(&self, x: K, y: K) -> bool {
         self.grid.get(&y).is_some_and(|row| row.contains_key(&x))
     }
-    pub fn entry(&mut self, x: K, y: K) -> std::collections::btree_map::Entry<K, D> {
+    pub fn entry(&mut self, x: K, y: K) -> std::collections::btree_map::Entry<'_, K, D> {
         self.grid.entry(y).or_default().entry(x)
     }
     pub fn get(&self, x: K, y: K) -> Option<&D> {
@@ -74,7 +74,7 @@ where
         self.grid.entry(y).or_default().insert(x, value)
     }
     pub fn into_values(self) -> impl Iterator<Item = D> {
-        self.grid.into_iter().flat_map(|(_, row)| row.into_values())
+        self.grid.into_values().flat_map(|row| row.into_values())
     }
     pub fn is_empty(&self) -> bool {
         self.grid.is_empty()
@@ -92,7 +92,7 @@ where
     pub fn keys(&self) -> impl Iterator<Item = (&K, &K)> {
         self.grid
             .iter()
-            .flat_map(|(y, row)| row.iter().map(move |(x, _)| (x, y)))
+            .flat_map(|(y, row)| row.keys().map(move |x| (x, y)))
     }
     pub fn len(&self) -> usize {
         self.grid.iter().map(|row| row.1.len()).sum()
@@ -115,9 +115,7 @@ where
         });
     }
     pub fn values(&self) -> impl Iterator<Item = &D> {
-        self.grid
-            .iter()
-            .flat_map(|(_, row)| row.iter().map(|(_, value)| value))
+        self.grid.values().flat_map(|row| row.values())
     }
 }
 
@@ -378,7 +376,7 @@ where
     K: Ord + Copy + Into<usize>,
     D: Eq,
 {
-    pub fn apply_path_finder(&self) -> path_finder::PathFinder<K, D> {
+    pub fn apply_path_finder(&self) -> path_finder::PathFinder<'_, K, D> {
         path_finder::PathFinder::new(self)
     }
 }
@@ -389,7 +387,7 @@ where
     <K as TryFrom<u8>>::Error: std::fmt::Debug,
     D: Eq + ToString,
 {
-    pub fn printer(&self) -> GridPrinter<K, D> {
+    pub fn printer(&self) -> GridPrinter<'_, K, D> {
         GridPrinter::new(self)
     }
 }
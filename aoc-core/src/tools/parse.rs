@@ -0,0 +1,178 @@
+use std::{any::type_name, marker::PhantomData};
+
+use crate::ParseError;
+
+/// How [`ints`] reads a `-` in front of a number, unsigned types never have a minus sign
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Minus {
+    /// Every `-` in front of a number is a minus sign: `3-5` is 3 and -5
+    Sign,
+    /// A `-` directly after a digit separates a range: `3-5` is 3 and 5, `-3--1` is -3 and -1
+    Range,
+}
+
+/// Integer types which [`ints`] can extract
+pub trait Integer: Sized {
+    const SIGNED: bool;
+
+    fn parse_int(text: &str) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($signed: expr, $($t: ty),+) => {
+        $(impl Integer for $t {
+            const SIGNED: bool = $signed;
+
+            fn parse_int(text: &str) -> Option<Self> {
+                text.parse().ok()
+            }
+        })+
+    };
+}
+
+impl_integer!(true, i8, i16, i32, i64, i128, isize);
+impl_integer!(false, u8, u16, u32, u64, u128, usize);
+
+/// Iterate over all integers in free-form text, skipping everything else
+///
+/// ```
+/// use aoc_core::tools::parse::{ints, Minus};
+///
+/// let numbers = ints::<i32>("p=3,-4 v=10-12", Minus::Range).collect::<Vec<_>>();
+/// assert_eq!(numbers, vec![3, -4, 10, 12]);
+/// ```
+///
+/// Panics when a number does not fit in `T`, pointing at its line and column; [`Ints::checked`] returns an error
+/// instead.
+pub fn ints<T: Integer>(text: &str, minus: Minus) -> Ints<'_, T> {
+    Ints {
+        text,
+        pos: 0,
+        minus,
+        _marker: PhantomData,
+    }
+}
+
+/// Iterator over the integers in a text, see [`ints`]
+#[derive(Debug, Clone)]
+pub struct Ints<'a, T> {
+    text: &'a str,
+    pos: usize,
+    minus: Minus,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T: Integer> Ints<'a, T> {
+    /// The integers as results, with an error for a number which does not fit in `T`
+    ///
+    /// ```
+    /// use aoc_core::tools::parse::{ints, Minus};
+    ///
+    /// let mut numbers = ints::<u8>("3 256", Minus::Sign).checked();
+    /// assert_eq!(numbers.next(), Some(Ok(3)));
+    /// assert!(numbers.next().unwrap().is_err());
+    /// ```
+    pub fn checked(self) -> CheckedInts<'a, T> {
+        CheckedInts(self)
+    }
+
+    fn next_checked(&mut self) -> Option<Result<T, ParseError>> {
+        let bytes = self.text.as_bytes();
+        let digits = bytes[self.pos..].iter().position(u8::is_ascii_digit)? + self.pos;
+        let end = bytes[digits..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .map_or(bytes.len(), |len| digits + len);
+        self.pos = end;
+
+        let after_digit = digits > 1 && bytes[digits - 2].is_ascii_digit();
+        let negative = T::SIGNED
+            && digits > 0
+            && bytes[digits - 1] == b'-'
+            && (self.minus == Minus::Sign || !after_digit);
+        let start = if negative { digits - 1 } else { digits };
+        let number = &self.text[start..end];
+        Some(T::parse_int(number).ok_or_else(|| {
+            let before = &self.text[..start];
+            let line = before.matches('\n').count() + 1;
+            let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+            ParseError::new(
+                line,
+                column,
+                format!("{} does not fit in {}", number, type_name::<T>()),
+            )
+        }))
+    }
+}
+
+impl<T: Integer> Iterator for Ints<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_checked()
+            .map(|number| number.unwrap_or_else(|e| panic!("{}", e)))
+    }
+}
+
+/// Iterator over the integers in a text as results, see [`Ints::checked`]
+#[derive(Debug, Clone)]
+pub struct CheckedInts<'a, T>(Ints<'a, T>);
+
+impl<T: Integer> Iterator for CheckedInts<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_checked()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minus_modes() {
+        let text = "3-5 -2, x=-7 1--4";
+        assert_eq!(
+            ints::<i32>(text, Minus::Sign).collect::<Vec<_>>(),
+            vec![3, -5, -2, -7, 1, -4]
+        );
+        assert_eq!(
+            ints::<i32>(text, Minus::Range).collect::<Vec<_>>(),
+            vec![3, 5, -2, -7, 1, -4]
+        );
+        assert_eq!(
+            ints::<u32>(text, Minus::Sign).collect::<Vec<_>>(),
+            vec![3, 5, 2, 7, 1, 4]
+        );
+    }
+
+    #[test]
+    fn test_free_form() {
+        assert_eq!(ints::<u8>("", Minus::Sign).next(), None);
+        assert_eq!(ints::<u8>("no numbers - here", Minus::Sign).next(), None);
+        assert_eq!(
+            ints::<u64>("Button A: X+94, Y+34\n\nPrize: X=8400", Minus::Range).collect::<Vec<_>>(),
+            vec![94, 34, 8400]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2, column 3: 256 does not fit in u8")]
+    fn test_overflow() {
+        ints::<u8>("1\n2 256", Minus::Sign).for_each(drop);
+    }
+
+    #[test]
+    fn test_checked_overflow() {
+        let numbers = ints::<i8>("1\n-128 -129 2", Minus::Sign)
+            .checked()
+            .collect::<Vec<_>>();
+        assert_eq!(numbers[..2], [Ok(1), Ok(-128)]);
+        assert_eq!(
+            numbers[2],
+            Err(ParseError::new(2, 6, "-129 does not fit in i8"))
+        );
+        assert_eq!(numbers[3], Ok(2), "continues after an error");
+    }
+}
@@ -0,0 +1,690 @@
+use std::{
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    encryption::{decrypt, encrypt, get_cache_passphrase, is_private, ENCRYPTED_EXTENSION},
+    fetcher::{fetch_puzzle_text, get_data_dir, is_offline},
+    AnswerLedger, AocDataType, AocError,
+};
+
+const MANIFEST_FILE_NAME: &str = "manifest";
+
+/// Messages AOC responds with instead of the requested data
+const ERROR_BODIES: [&str; 3] = [
+    "Please don't repeatedly request this endpoint",
+    "Puzzle inputs differ by user",
+    "Please log in",
+];
+
+/// Check whether data looks like what AOC returns instead of actual puzzle data
+pub fn validate_data(file_name: &str, data: &str) -> Result<(), String> {
+    let trimmed = data.trim_start();
+    if trimmed.is_empty() && !file_name.starts_with("example-") {
+        return Err("file is empty".to_string());
+    }
+    if let Some(message) = ERROR_BODIES.iter().find(|m| data.contains(*m)) {
+        return Err(format!("contains AOC error message \"{}\"", message));
+    }
+    let lowercase_start = trimmed
+        .chars()
+        .take(14)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if lowercase_start.starts_with("<!doctype html") || lowercase_start.starts_with("<html") {
+        return Err("contains an HTML page".to_string());
+    }
+    Ok(())
+}
+
+/// Validate and write a file to the cache of a puzzle day, and record it in the manifest
+///
+/// Input, text and examples are encrypted when a cache passphrase is set, the plain text file is removed in that
+/// case. The manifest records the size and hash of the bytes on disk, the ciphertext when the file is encrypted.
+pub(crate) fn write_cache_file(
+    day: u32,
+    year: u32,
+    file_name: &str,
+    data: &str,
+) -> Result<(), AocError> {
+    validate_data(file_name, data).map_err(|reason| AocError::InvalidData {
+        file: file_name.to_string(),
+        reason,
+    })?;
+    let data_dir = get_data_dir(day, year)?;
+    fs::create_dir_all(&data_dir)?;
+    let stored = match get_cache_passphrase().filter(|_| is_private(file_name)) {
+        Some(passphrase) => {
+            let encrypted = encrypt(&passphrase, data);
+            fs::write(data_dir.join(encrypted_file_name(file_name)), &encrypted)?;
+            remove_if_exists(data_dir.join(file_name))?;
+            encrypted
+        }
+        None => {
+            fs::write(data_dir.join(file_name), data)?;
+            data.to_string()
+        }
+    };
+    let mut manifest = CacheManifest::load(day, year)?;
+    manifest.record(file_name, &stored);
+    manifest.save()
+}
+
+/// Read a file from the cache of a puzzle day, decrypting it when only an encrypted copy exists
+pub(crate) fn read_cache_file(
+    day: u32,
+    year: u32,
+    file_name: &str,
+) -> Result<Option<String>, AocError> {
+    let data_dir = get_data_dir(day, year)?;
+    let path = data_dir.join(file_name);
+    if path.exists() {
+        return Ok(Some(fs::read_to_string(path)?));
+    }
+    let path = data_dir.join(encrypted_file_name(file_name));
+    if !path.exists() {
+        return Ok(None);
+    }
+    let Some(passphrase) = get_cache_passphrase() else {
+        return Err(AocError::MissingCacheKey(path));
+    };
+    let content = fs::read_to_string(&path)?;
+    decrypt(&passphrase, &content)
+        .map(Some)
+        .map_err(|reason| AocError::CacheCorrupt { path, reason })
+}
+
+/// Read a file from the cache of a puzzle day as it is stored, encrypted when only an encrypted copy exists
+fn read_stored_file(day: u32, year: u32, file_name: &str) -> Result<Option<String>, AocError> {
+    match stored_file_path(day, year, file_name)? {
+        Some(path) => Ok(Some(fs::read_to_string(path)?)),
+        None => Ok(None),
+    }
+}
+
+/// Path of a cached file as it is stored, the encrypted copy when there is no plain text file
+fn stored_file_path(day: u32, year: u32, file_name: &str) -> Result<Option<PathBuf>, AocError> {
+    let data_dir = get_data_dir(day, year)?;
+    Ok([
+        data_dir.join(file_name),
+        data_dir.join(encrypted_file_name(file_name)),
+    ]
+    .into_iter()
+    .find(|path| path.exists()))
+}
+
+/// Encrypt the plain text input, text and examples of a puzzle day, returns the number of encrypted files
+pub fn encrypt_cache(day: u32, year: u32) -> Result<usize, AocError> {
+    let data_dir = get_data_dir(day, year)?;
+    if !data_dir.exists() {
+        return Ok(0);
+    }
+    let mut file_names = fs::read_dir(&data_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_private(name))
+        .collect::<Vec<_>>();
+    file_names.sort();
+    let mut manifest = CacheManifest::load(day, year)?;
+    for file_name in &file_names {
+        let path = data_dir.join(file_name);
+        let Some(passphrase) = get_cache_passphrase() else {
+            return Err(AocError::MissingCacheKey(path));
+        };
+        let encrypted = encrypt(&passphrase, &fs::read_to_string(&path)?);
+        fs::write(data_dir.join(encrypted_file_name(file_name)), &encrypted)?;
+        fs::remove_file(path)?;
+        manifest.update_stored(file_name, &encrypted);
+    }
+    manifest.save()?;
+    Ok(file_names.len())
+}
+
+fn encrypted_file_name(file_name: &str) -> String {
+    format!("{}.{}", file_name, ENCRYPTED_EXTENSION)
+}
+
+fn remove_if_exists(path: PathBuf) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub file: String,
+    /// Size of the stored file, the encrypted file for encrypted data
+    pub size: usize,
+    /// Hash of the stored file, the encrypted file for encrypted data
+    pub sha256: String,
+    /// Unix timestamp in seconds
+    pub fetched_at: u64,
+    /// Number of puzzle parts on the page the puzzle text was taken from
+    pub parts: Option<usize>,
+}
+
+/// Size, hash and fetch time of the cached files of a puzzle day
+///
+/// Stored as `aoc_data/<year>/<day>/manifest`, one tab separated `file size sha256 fetched_at [parts]` line per file.
+#[derive(Debug)]
+pub struct CacheManifest {
+    path: PathBuf,
+    entries: Vec<ManifestEntry>,
+}
+
+impl CacheManifest {
+    pub fn load(day: u32, year: u32) -> Result<Self, AocError> {
+        let path = get_data_dir(day, year)?.join(MANIFEST_FILE_NAME);
+        let entries = if path.exists() {
+            fs::read_to_string(&path)?
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(parse_entry)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|reason| AocError::CacheCorrupt {
+                    path: path.clone(),
+                    reason,
+                })?
+        } else {
+            vec![]
+        };
+        Ok(Self { path, entries })
+    }
+
+    pub fn get(&self, file: &str) -> Option<&ManifestEntry> {
+        self.entries.iter().find(|e| e.file == file)
+    }
+
+    fn record(&mut self, file: &str, stored: &str) {
+        self.entries.retain(|e| e.file != file);
+        self.entries.push(ManifestEntry {
+            file: file.to_string(),
+            size: stored.len(),
+            sha256: hash(stored),
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            parts: None,
+        });
+    }
+
+    /// Update the size and hash of a file which is stored differently, keeping when it was fetched
+    fn update_stored(&mut self, file: &str, stored: &str) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.file == file) {
+            entry.size = stored.len();
+            entry.sha256 = hash(stored);
+        }
+    }
+
+    fn set_parts(&mut self, file: &str, parts: usize) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.file == file) {
+            entry.parts = Some(parts);
+        }
+    }
+
+    fn save(&self) -> Result<(), AocError> {
+        let content = self
+            .entries
+            .iter()
+            .map(|e| {
+                let parts = e.parts.map(|p| format!("\t{}", p)).unwrap_or_default();
+                format!(
+                    "{}\t{}\t{}\t{}{}\n",
+                    e.file, e.size, e.sha256, e.fetched_at, parts
+                )
+            })
+            .collect::<String>();
+        fs::create_dir_all(self.path.parent().expect("Day directory"))?;
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+fn parse_entry(line: &str) -> Result<ManifestEntry, String> {
+    let invalid = || format!("invalid manifest entry: {}", line);
+    let fields = line.split('\t').collect::<Vec<_>>();
+    let (file, size, sha256, fetched_at, parts) = match fields[..] {
+        [file, size, sha256, fetched_at] => (file, size, sha256, fetched_at, None),
+        [file, size, sha256, fetched_at, parts] => (file, size, sha256, fetched_at, Some(parts)),
+        _ => return Err(invalid()),
+    };
+    Ok(ManifestEntry {
+        file: file.to_string(),
+        size: size.parse().map_err(|_| invalid())?,
+        sha256: sha256.to_string(),
+        fetched_at: fetched_at.parse().map_err(|_| invalid())?,
+        parts: parts
+            .map(|p| p.parse().map_err(|_| invalid()))
+            .transpose()?,
+    })
+}
+
+fn hash(data: &str) -> String {
+    format!("{:x}", Sha256::digest(data.as_bytes()))
+}
+
+/// A suspicious file in the cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheIssue {
+    pub file: String,
+    pub reason: String,
+}
+
+/// Check all cached files of a puzzle day for error pages and changes since they were fetched
+pub fn check_cache(day: u32, year: u32) -> Result<Vec<CacheIssue>, AocError> {
+    let data_dir = get_data_dir(day, year)?;
+    if !data_dir.exists() {
+        return Ok(vec![]);
+    }
+    let manifest = CacheManifest::load(day, year)?;
+    let mut files = fs::read_dir(&data_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .map(
+            |name| match name.strip_suffix(&format!(".{}", ENCRYPTED_EXTENSION)) {
+                Some(plain_name) => plain_name.to_string(),
+                None => name,
+            },
+        )
+        .filter(|name| is_cached_data(name))
+        .collect::<Vec<_>>();
+    files.sort();
+    files.dedup();
+
+    let mut issues = vec![];
+    for file in files {
+        let data = match read_cache_file(day, year, &file) {
+            Ok(data) => data.unwrap_or_default(),
+            Err(AocError::MissingCacheKey(_)) => {
+                issues.push(CacheIssue {
+                    file,
+                    reason: "encrypted, set AOC_CACHE_KEY to check it".to_string(),
+                });
+                continue;
+            }
+            Err(AocError::CacheCorrupt { reason, .. }) => {
+                issues.push(CacheIssue { file, reason });
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let stored = read_stored_file(day, year, &file)?.unwrap_or_default();
+        let reason = match (validate_data(&file, &data), manifest.get(&file)) {
+            (Err(reason), _) => reason,
+            (Ok(_), None) => "not in manifest, fetched before validation".to_string(),
+            (Ok(_), Some(entry)) if entry.size != stored.len() || entry.sha256 != hash(&stored) => {
+                "changed since it was fetched".to_string()
+            }
+            _ => continue,
+        };
+        issues.push(CacheIssue { file, reason });
+    }
+    Ok(issues)
+}
+
+fn is_cached_data(file_name: &str) -> bool {
+    file_name == AocDataType::Input.file_name()
+        || file_name == AocDataType::Text.file_name()
+        || file_name.starts_with("example-")
+}
+
+/// Whether data of a puzzle day is in the cache, in plain text or encrypted
+pub fn is_cached(data_type: &AocDataType, day: u32, year: u32) -> bool {
+    stored_file_path(day, year, &data_type.file_name()).is_ok_and(|path| path.is_some())
+}
+
+/// Download the input and text of a puzzle day again, replacing the cached files
+pub fn refetch(day: u32, year: u32) -> Result<(), AocError> {
+    let input = AocDataType::Input.fetch(day, year)?;
+    write_cache_file(day, year, &AocDataType::Input.file_name(), &input)?;
+    store_puzzle_text(day, year)?;
+    Ok(())
+}
+
+/// Download and cache the puzzle text, recording how many parts the page has unlocked
+///
+/// The answer ledger is created along with it, so builds can track it with [`text_refresh_trigger`].
+pub(crate) fn store_puzzle_text(day: u32, year: u32) -> Result<String, AocError> {
+    let (text, parts) = fetch_puzzle_text(day, year)?;
+    let file_name = AocDataType::Text.file_name();
+    write_cache_file(day, year, &file_name, &text)?;
+    let mut manifest = CacheManifest::load(day, year)?;
+    manifest.set_parts(&file_name, parts);
+    manifest.save()?;
+    let ledger = AnswerLedger::load(day, year)?;
+    if !ledger.path().exists() {
+        ledger.save()?;
+    }
+    Ok(text)
+}
+
+/// Whether the cached puzzle text lacks the part 2 description, while part 1 has been solved since
+pub(crate) fn needs_text_refresh(day: u32, year: u32, text: &str) -> bool {
+    !is_offline()
+        && cached_text_parts(day, year, text) < 2
+        && AnswerLedger::load(day, year)
+            .ok()
+            .is_some_and(|ledger| ledger.correct_answer(1).is_some())
+}
+
+/// File which changes when refreshing the cached puzzle text becomes worthwhile
+///
+/// While the text lacks the part 2 description this is the answer ledger, so builds depending on it can be rerun
+/// once part 1 is solved. Returns `None` when the text is not cached, or there is no ledger to track.
+pub fn text_refresh_trigger(day: u32, year: u32) -> Result<Option<PathBuf>, AocError> {
+    let Some(text) = read_cache_file(day, year, &AocDataType::Text.file_name())? else {
+        return Ok(None);
+    };
+    if cached_text_parts(day, year, &text) >= 2 {
+        return Ok(None);
+    }
+    let ledger = AnswerLedger::load(day, year)?;
+    Ok(Some(ledger.path().to_path_buf()).filter(|path| path.exists()))
+}
+
+/// Read the puzzle text from the cache only, for builds which must not connect to AOC
+///
+/// Returns `None` when the text is not cached yet.
+pub fn cached_puzzle_text(day: u32, year: u32) -> Result<Option<String>, AocError> {
+    let file_name = AocDataType::Text.file_name();
+    let Some(text) = read_cache_file(day, year, &file_name)? else {
+        return Ok(None);
+    };
+    validate_cached(day, year, &file_name, &text)?;
+    Ok(Some(text))
+}
+
+/// The cached puzzle text file, so builds reading it from the cache are rerun when it is updated
+///
+/// This is `text.md`, or `text.md.enc` when the cache is encrypted. Returns `None` when the text is not cached.
+pub fn cached_text_trigger(day: u32, year: u32) -> Option<PathBuf> {
+    stored_file_path(day, year, &AocDataType::Text.file_name())
+        .ok()
+        .flatten()
+}
+
+/// Download the puzzle text when it is not cached, or lacks the part 2 description while part 1 is solved
+///
+/// Returns whether the text was downloaded.
+pub fn update_puzzle_text(day: u32, year: u32) -> Result<bool, AocError> {
+    match read_cache_file(day, year, &AocDataType::Text.file_name())? {
+        Some(text) if !needs_text_refresh(day, year, &text) => Ok(false),
+        _ => store_puzzle_text(day, year).map(|_| true),
+    }
+}
+
+/// Reject cached data which looks like an error page
+pub(crate) fn validate_cached(
+    day: u32,
+    year: u32,
+    file_name: &str,
+    data: &str,
+) -> Result<(), AocError> {
+    let data_dir = get_data_dir(day, year)?;
+    validate_data(file_name, data).map_err(|reason| AocError::CacheCorrupt {
+        path: data_dir.join(file_name),
+        reason: format!("{}, replace it with --refetch {}", reason, day),
+    })
+}
+
+fn cached_text_parts(day: u32, year: u32, text: &str) -> usize {
+    let file_name = AocDataType::Text.file_name();
+    CacheManifest::load(day, year)
+        .ok()
+        .and_then(|manifest| manifest.get(&file_name).and_then(|e| e.parts))
+        // Text cached before the manifest existed
+        .unwrap_or_else(|| {
+            if text.contains("--- Part Two ---") {
+                2
+            } else {
+                1
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        get_aoc_data, set_cache_passphrase, set_transport, test_utils::TestEnv, FixtureTransport,
+        HttpMethod, HttpResponse,
+    };
+
+    #[test]
+    fn test_validate_data() {
+        assert!(validate_data("input", "1\n2\n").is_ok());
+        assert!(validate_data("input", "").is_err());
+        assert!(validate_data("example-1", "").is_ok());
+        assert!(validate_data(
+            "input",
+            "Please don't repeatedly request this endpoint before it unlocks!"
+        )
+        .is_err());
+        assert!(validate_data(
+            "input",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+        )
+        .is_err());
+        assert!(validate_data("input", "\n<!DOCTYPE html>\n<html lang=\"en-us\">").is_err());
+    }
+
+    #[test]
+    fn test_invalid_data_not_cached() {
+        let env = TestEnv::new(
+            "invalid-data",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/day/1/input",
+                HttpResponse::new(200, "<!DOCTYPE html><html><body>Log in</body></html>"),
+            ),
+        );
+        let result = get_aoc_data(AocDataType::Input, 1, 2025);
+        assert!(matches!(result, Err(AocError::InvalidData { .. })));
+        assert!(!env.data_dir.join("2025/1/input").exists());
+    }
+
+    #[test]
+    fn test_check_and_refetch() {
+        let env = TestEnv::new(
+            "check-cache",
+            FixtureTransport::new()
+                .with_response(
+                    HttpMethod::Get,
+                    "2025/day/1/input",
+                    HttpResponse::new(200, "1\n2\n"),
+                )
+                .with_response(
+                    HttpMethod::Get,
+                    "2025/day/1",
+                    HttpResponse::new(
+                        200,
+                        r#"<article class="day-desc"><h2>--- Day 1 ---</h2></article>"#,
+                    ),
+                ),
+        );
+        assert!(!is_cached(&AocDataType::Input, 1, 2025));
+        get_aoc_data(AocDataType::Input, 1, 2025).unwrap();
+        assert!(is_cached(&AocDataType::Input, 1, 2025));
+        assert!(!is_cached(&AocDataType::Text, 1, 2025));
+        assert_eq!(check_cache(1, 2025).unwrap(), vec![]);
+
+        let input_path = env.data_dir.join("2025/1/input");
+        fs::write(&input_path, "Please don't repeatedly request this endpoint").unwrap();
+        fs::write(env.data_dir.join("2025/1/text.md"), "Day 1").unwrap();
+        let issues = check_cache(1, 2025).unwrap();
+        assert_eq!(
+            issues.iter().map(|i| i.file.as_str()).collect::<Vec<_>>(),
+            vec!["input", "text.md"]
+        );
+        assert!(matches!(
+            get_aoc_data(AocDataType::Input, 1, 2025),
+            Err(AocError::CacheCorrupt { .. })
+        ));
+
+        refetch(1, 2025).unwrap();
+        assert_eq!(check_cache(1, 2025).unwrap(), vec![]);
+        assert_eq!(fs::read_to_string(&input_path).unwrap(), "1\n2\n");
+    }
+
+    #[test]
+    fn test_text_refresh_after_part1() {
+        let part1 =
+            r#"<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Part one</p></article>"#;
+        let part2 = format!(
+            r#"{}<p>Your puzzle answer was <code>3</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Part two</p></article>"#,
+            part1
+        );
+        let env = TestEnv::new(
+            "text-refresh",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/day/1",
+                HttpResponse::new(200, part1),
+            ),
+        );
+        let text = get_aoc_data(AocDataType::Text, 1, 2025).unwrap();
+        assert!(!text.contains("Part two"));
+        let manifest = CacheManifest::load(1, 2025).unwrap();
+        assert_eq!(manifest.get("text.md").unwrap().parts, Some(1));
+        assert_eq!(
+            text_refresh_trigger(1, 2025).unwrap(),
+            Some(env.data_dir.join("2025/1/answers"))
+        );
+
+        // Unsolved, the cached text is used
+        get_aoc_data(AocDataType::Text, 1, 2025).unwrap();
+        assert_eq!(env.transport.requests().len(), 1);
+
+        let transport = Arc::new(FixtureTransport::new().with_response(
+            HttpMethod::Get,
+            "2025/day/1",
+            HttpResponse::new(200, &part2),
+        ));
+        set_transport(transport.clone());
+        AnswerLedger::load(1, 2025)
+            .unwrap()
+            .record_correct(1, "3")
+            .unwrap();
+        let text = get_aoc_data(AocDataType::Text, 1, 2025).unwrap();
+        assert!(text.contains("Part two"));
+        let manifest = CacheManifest::load(1, 2025).unwrap();
+        assert_eq!(manifest.get("text.md").unwrap().parts, Some(2));
+        assert_eq!(text_refresh_trigger(1, 2025).unwrap(), None);
+
+        // Both parts cached, no more refreshing
+        get_aoc_data(AocDataType::Text, 1, 2025).unwrap();
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_cache_only_text() {
+        let part1 =
+            r#"<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Part one</p></article>"#;
+        let env = TestEnv::new(
+            "cache-only-text",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/day/1",
+                HttpResponse::new(200, part1),
+            ),
+        );
+        assert_eq!(cached_text_trigger(1, 2025), None);
+        assert!(!env.data_dir.join("2025/1").exists(), "read-only");
+        assert_eq!(cached_puzzle_text(1, 2025).unwrap(), None);
+        assert!(env.transport.requests().is_empty());
+
+        // The doc generation step downloads the text once, until part 1 is solved
+        assert!(update_puzzle_text(1, 2025).unwrap());
+        assert!(!update_puzzle_text(1, 2025).unwrap());
+        assert_eq!(env.transport.requests().len(), 1);
+        assert!(cached_puzzle_text(1, 2025)
+            .unwrap()
+            .is_some_and(|text| text.contains("Part one")));
+        assert_eq!(
+            cached_text_trigger(1, 2025),
+            Some(env.data_dir.join("2025/1/text.md"))
+        );
+
+        set_cache_passphrase(Some("secret".to_string()));
+        encrypt_cache(1, 2025).unwrap();
+        assert_eq!(
+            cached_text_trigger(1, 2025),
+            Some(env.data_dir.join("2025/1/text.md.enc"))
+        );
+        set_cache_passphrase(None);
+
+        fs::write(env.data_dir.join("2025/1/text.md"), "Please log in").unwrap();
+        assert!(matches!(
+            cached_puzzle_text(1, 2025),
+            Err(AocError::CacheCorrupt { .. })
+        ));
+    }
+
+    #[test]
+    fn test_encrypted_cache() {
+        let env = TestEnv::new(
+            "encrypted",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/day/1/input",
+                HttpResponse::new(200, "1\n2\n"),
+            ),
+        );
+        set_cache_passphrase(Some("secret".to_string()));
+        assert_eq!(get_aoc_data(AocDataType::Input, 1, 2025).unwrap(), "1\n2\n");
+        let encrypted_path = env.data_dir.join("2025/1/input.enc");
+        assert!(!env.data_dir.join("2025/1/input").exists());
+        assert!(is_cached(&AocDataType::Input, 1, 2025));
+        assert!(!fs::read_to_string(&encrypted_path)
+            .unwrap()
+            .contains("1\n2\n"));
+        assert_eq!(get_aoc_data(AocDataType::Input, 1, 2025).unwrap(), "1\n2\n");
+        assert_eq!(
+            env.transport.requests().len(),
+            1,
+            "decrypted from the cache"
+        );
+        assert_eq!(check_cache(1, 2025).unwrap(), vec![]);
+
+        set_cache_passphrase(Some("wrong".to_string()));
+        assert!(matches!(
+            get_aoc_data(AocDataType::Input, 1, 2025),
+            Err(AocError::CacheCorrupt { .. })
+        ));
+        set_cache_passphrase(None);
+        assert!(matches!(
+            get_aoc_data(AocDataType::Input, 1, 2025),
+            Err(AocError::MissingCacheKey(_))
+        ));
+        assert_eq!(check_cache(1, 2025).unwrap().len(), 1);
+
+        // Plain text caches can be encrypted afterwards
+        fs::remove_file(&encrypted_path).unwrap();
+        get_aoc_data(AocDataType::Input, 1, 2025).unwrap();
+        set_cache_passphrase(Some("secret".to_string()));
+        assert_eq!(encrypt_cache(1, 2025).unwrap(), 1);
+        assert!(encrypted_path.exists());
+        assert_eq!(get_aoc_data(AocDataType::Input, 1, 2025).unwrap(), "1\n2\n");
+
+        // The manifest describes the encrypted file, not the puzzle data
+        let entry = CacheManifest::load(1, 2025)
+            .unwrap()
+            .get("input")
+            .cloned()
+            .unwrap();
+        assert_eq!(
+            entry.sha256,
+            hash(&fs::read_to_string(&encrypted_path).unwrap())
+        );
+        assert_ne!(entry.sha256, hash("1\n2\n"));
+        assert_eq!(check_cache(1, 2025).unwrap(), vec![]);
+    }
+}
@@ -0,0 +1,61 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+lazy_static::lazy_static! {
+    static ref CLOCK: Mutex<Arc<dyn Clock>> = Mutex::new(Arc::new(SystemClock));
+}
+
+/// Set the clock used to decide whether puzzles are released, and to wait for their release.
+pub fn set_clock(clock: Arc<dyn Clock>) {
+    let mut current = CLOCK.lock().unwrap();
+    *current = clock;
+}
+
+pub(crate) fn get_clock() -> Arc<dyn Clock> {
+    CLOCK.lock().unwrap().clone()
+}
+
+/// Source of the current time, and a way to let it pass
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// Clock using the system time
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Clock standing still until it is told to sleep, sleeping advances it instantly
+pub struct FixedClock {
+    now: Mutex<SystemTime>,
+}
+
+impl FixedClock {
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
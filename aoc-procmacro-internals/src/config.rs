@@ -0,0 +1,140 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::AocError;
+
+pub const AOC_CONFIG_FILE_NAME: &str = "aoc.toml";
+
+lazy_static::lazy_static! {
+    static ref CONFIG: Result<AocConfig, AocError> = AocConfig::find();
+    static ref DEFAULT_CONFIG: AocConfig = AocConfig::default();
+}
+
+/// Project configuration, read from the `aoc.toml` file
+///
+/// Relative paths are relative to the directory of the `aoc.toml` file. Environment variables and command line
+/// arguments take precedence over these settings.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AocConfig {
+    /// Base url of the AOC website, e.g. a local mirror or stub server
+    pub base_url: Option<String>,
+    /// Directory to cache puzzle data in
+    pub data_dir: Option<PathBuf>,
+    /// Year of the puzzles, used when the `aoc` attribute does not set one
+    pub year: Option<u32>,
+    /// File containing the AOC session
+    pub session_file: Option<PathBuf>,
+    /// Only use cached puzzle data, never connect to AOC
+    pub offline: Option<bool>,
+    /// Minimum number of seconds between two requests to AOC
+    pub throttle: Option<f64>,
+    /// Id of the private leaderboard to show by default
+    pub leaderboard: Option<u64>,
+    /// Download missing puzzle texts while building, when `false` the `aoc_puzzle` macro only reads the cache
+    pub fetch_in_build: Option<bool>,
+}
+
+impl AocConfig {
+    /// Find and read the `aoc.toml` file, an empty config is returned when there is none
+    pub fn find() -> Result<Self, AocError> {
+        search_dirs()
+            .iter()
+            .flat_map(|dir| dir.ancestors())
+            .map(|dir| dir.join(AOC_CONFIG_FILE_NAME))
+            .find(|path| path.exists())
+            .map_or_else(|| Ok(Self::default()), |path| Self::load(&path))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| AocError::Config(format!("Could not read {}: {}", path.display(), e)))?;
+        Self::parse(&content, path.parent().unwrap_or(Path::new(".")))
+            .map_err(|e| AocError::Config(format!("Invalid {}: {}", path.display(), e)))
+    }
+
+    fn parse(content: &str, base_dir: &Path) -> Result<Self, String> {
+        let mut config: Self = toml::from_str(content).map_err(|e| e.to_string())?;
+        config.data_dir = config.data_dir.map(|dir| base_dir.join(dir));
+        config.session_file = config.session_file.map(|file| base_dir.join(file));
+        Ok(config)
+    }
+
+    /// Read the session from the session file, if configured
+    pub fn session(&self) -> Option<String> {
+        let session = fs::read_to_string(self.session_file.as_ref()?).ok()?;
+        Some(session.trim().to_string()).filter(|s| !s.is_empty())
+    }
+}
+
+/// The project configuration, read once from the `aoc.toml` file
+///
+/// Fails when the `aoc.toml` file is invalid, the entry points report that: the runner on start and the macros
+/// with a compile error.
+pub fn get_config() -> Result<&'static AocConfig, &'static AocError> {
+    CONFIG.as_ref()
+}
+
+/// The project configuration, or the defaults when the `aoc.toml` file is invalid
+///
+/// For settings which are read on first use, after the entry points had the chance to report the error.
+pub(crate) fn get_config_or_default() -> &'static AocConfig {
+    get_config().unwrap_or(&DEFAULT_CONFIG)
+}
+
+/// Directories to search the `aoc.toml` file and `aoc_data` directory from
+///
+/// Cargo sets the manifest directory for `cargo run`, `cargo test`, `cargo bench` and proc macro expansion, whose
+/// current directories differ. It is searched first, so all of them find the same files.
+pub(crate) fn search_dirs() -> Vec<PathBuf> {
+    env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .into_iter()
+        .chain(env::current_dir().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = AocConfig::parse(
+            r#"
+base_url = "http://localhost:8080/"
+data_dir = "aoc_data"
+year = 2025
+session_file = "/home/user/.aoc-session"
+offline = true
+throttle = 2.5
+leaderboard = 12345
+fetch_in_build = false
+"#,
+            Path::new("/project"),
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            AocConfig {
+                base_url: Some("http://localhost:8080/".to_string()),
+                data_dir: Some(PathBuf::from("/project/aoc_data")),
+                year: Some(2025),
+                session_file: Some(PathBuf::from("/home/user/.aoc-session")),
+                offline: Some(true),
+                throttle: Some(2.5),
+                leaderboard: Some(12345),
+                fetch_in_build: Some(false),
+            }
+        );
+        assert_eq!(
+            AocConfig::parse("", Path::new("/project")).unwrap(),
+            AocConfig::default()
+        );
+        assert!(AocConfig::parse("data_dr = \"typo\"", Path::new("/project")).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use std::{collections::HashMap, env, sync::Mutex};
+
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, OsRng},
+    AeadCore, ChaCha20Poly1305, Key, KeyInit, Nonce,
+};
+use sha2::Sha256;
+
+pub const AOC_CACHE_KEY_ENV_VAR: &str = "AOC_CACHE_KEY";
+/// Extension of encrypted cache files, e.g. `input.enc`
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+const ENCRYPTED_HEADER: &str = "aoc-encrypted-v1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KDF_ROUNDS: u32 = 100_000;
+
+lazy_static::lazy_static! {
+    static ref CACHE_PASSPHRASE: Mutex<Option<String>> = Mutex::new(
+        env::var(AOC_CACHE_KEY_ENV_VAR).ok().filter(|v| !v.is_empty())
+    );
+    /// Salt of the files encrypted by this process, so their key is derived only once
+    static ref SALT: [u8; SALT_LEN] = {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        salt
+    };
+    /// Keys derived from a passphrase and salt, key derivation is slow on purpose
+    static ref DERIVED_KEYS: Mutex<HashMap<(String, Vec<u8>), Key>> = Mutex::new(HashMap::new());
+}
+
+/// Set the passphrase to encrypt the cached input and text with, `None` stores them in plain text.
+pub fn set_cache_passphrase(passphrase: Option<String>) {
+    let mut current = CACHE_PASSPHRASE.lock().unwrap();
+    *current = passphrase;
+}
+
+pub(crate) fn get_cache_passphrase() -> Option<String> {
+    CACHE_PASSPHRASE.lock().unwrap().clone()
+}
+
+/// Whether a cached file holds personal or copyrighted puzzle data, which is encrypted when a passphrase is set
+pub(crate) fn is_private(file_name: &str) -> bool {
+    file_name == "input" || file_name == "text.md" || file_name.starts_with("example-")
+}
+
+/// Encrypt data into a text file: a header line followed by the hex encoded salt, nonce and ciphertext
+///
+/// The salt is shared by all files encrypted in the same process, the nonce is random per file.
+pub(crate) fn encrypt(passphrase: &str, data: &str) -> String {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher(passphrase, SALT.as_slice())
+        .encrypt(&nonce, data.as_bytes())
+        .expect("Encrypt cache data");
+    let payload = [SALT.as_slice(), nonce.as_slice(), &ciphertext].concat();
+    format!("{}\n{}\n", ENCRYPTED_HEADER, to_hex(&payload))
+}
+
+/// Decrypt a file created by [`encrypt`]
+pub(crate) fn decrypt(passphrase: &str, content: &str) -> Result<String, String> {
+    let payload = content
+        .strip_prefix(ENCRYPTED_HEADER)
+        .and_then(|hex| from_hex(hex.trim()))
+        .filter(|payload| payload.len() >= SALT_LEN + NONCE_LEN)
+        .ok_or_else(|| "not an encrypted cache file".to_string())?;
+    let (salt, rest) = payload.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let data = cipher(passphrase, salt)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| format!("decryption failed, is {} correct?", AOC_CACHE_KEY_ENV_VAR))?;
+    String::from_utf8(data).map_err(|e| e.to_string())
+}
+
+fn cipher(passphrase: &str, salt: &[u8]) -> ChaCha20Poly1305 {
+    let mut keys = DERIVED_KEYS.lock().unwrap();
+    let key = keys
+        .entry((passphrase.to_string(), salt.to_vec()))
+        .or_insert_with(|| {
+            let mut key = Key::default();
+            pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, KDF_ROUNDS, &mut key);
+            key
+        });
+    ChaCha20Poly1305::new(key)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let encrypted = encrypt("secret", "1\n2\n");
+        assert!(encrypted.starts_with(ENCRYPTED_HEADER));
+        assert!(!encrypted.contains("1\n2\n"));
+        assert_ne!(encrypted, encrypt("secret", "1\n2\n"), "nonce is random");
+        assert_eq!(decrypt("secret", &encrypted).unwrap(), "1\n2\n");
+        assert!(decrypt("wrong", &encrypted).is_err());
+        assert!(decrypt("secret", "1\n2\n").is_err());
+    }
+}
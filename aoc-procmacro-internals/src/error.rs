@@ -0,0 +1,117 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::{format_countdown, user_session_file, AOC_CACHE_KEY_ENV_VAR};
+
+/// Errors while fetching, caching or submitting AOC data
+#[derive(Debug)]
+pub enum AocError {
+    /// No session is available to authenticate with AOC
+    MissingSession,
+    /// AOC does not accept the session, it expired or is invalid
+    SessionExpired,
+    /// The request could not be sent or the response could not be read
+    Transport(String),
+    /// AOC responded with an unsuccessful status
+    HttpStatus { status: u16, body: String },
+    /// AOC asks to slow down
+    RateLimited { retry_after: Option<String> },
+    /// Reading or writing the `aoc_data` cache failed
+    Io(std::io::Error),
+    /// A file in the `aoc_data` cache has unexpected content
+    CacheCorrupt { path: PathBuf, reason: String },
+    /// A cache file is encrypted and no passphrase is available to decrypt it
+    MissingCacheKey(PathBuf),
+    /// Data from AOC looks like an error page and was not cached
+    InvalidData { file: String, reason: String },
+    /// Data from AOC could not be understood
+    Parse(String),
+    /// The requested data does not exist, e.g. an example which is not in the puzzle text
+    NotFound(String),
+    /// An answer was not submitted, e.g. because it is known to be wrong
+    Refused(String),
+    /// Offline mode is enabled and the data is not cached
+    Offline(String),
+    /// The `aoc.toml` file could not be read or is invalid
+    Config(String),
+    /// The puzzle is not released yet
+    NotReleased {
+        day: u32,
+        year: u32,
+        remaining: Duration,
+    },
+}
+
+impl std::fmt::Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::MissingSession => write!(
+                f,
+                "AOC session unavailable, set AOC_SESSION, pass --aoc-session or store it in {}",
+                user_session_file().map_or("~/.config/aoc/session".to_string(), |f| f
+                    .display()
+                    .to_string())
+            ),
+            AocError::SessionExpired => write!(
+                f,
+                "AOC session expired or invalid, log in to AOC again and update the session"
+            ),
+            AocError::Transport(e) => write!(f, "request to AOC failed: {}", e),
+            AocError::HttpStatus { status, body } => {
+                write!(f, "AOC responded with status {}: {}", status, body.trim())
+            }
+            AocError::RateLimited { retry_after } => {
+                write!(f, "AOC is rate limiting requests (429 Too Many Requests)")?;
+                if let Some(retry_after) = retry_after {
+                    write!(f, ", retry after {}s", retry_after)?;
+                }
+                write!(f, ", slow down before trying again")
+            }
+            AocError::Io(e) => write!(f, "cache access failed: {}", e),
+            AocError::CacheCorrupt { path, reason } => {
+                write!(f, "corrupt cache file {}: {}", path.display(), reason)
+            }
+            AocError::MissingCacheKey(path) => write!(
+                f,
+                "cache encryption key unavailable for {}, set {}",
+                path.display(),
+                AOC_CACHE_KEY_ENV_VAR
+            ),
+            AocError::InvalidData { file, reason } => {
+                write!(f, "refusing to cache {}: {}", file, reason)
+            }
+            AocError::Parse(e) => write!(f, "could not parse AOC data: {}", e),
+            AocError::NotFound(e) => write!(f, "not found: {}", e),
+            AocError::Refused(e) => write!(f, "refused: {}", e),
+            AocError::Offline(path) => {
+                write!(f, "offline mode is enabled and {} is not cached", path)
+            }
+            AocError::Config(e) => write!(f, "{}", e),
+            AocError::NotReleased {
+                day,
+                year,
+                remaining,
+            } => write!(
+                f,
+                "day {} of {} is not released yet, it unlocks in {}",
+                day,
+                year,
+                format_countdown(*remaining)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for AocError {
+    fn from(value: std::io::Error) -> Self {
+        AocError::Io(value)
+    }
+}
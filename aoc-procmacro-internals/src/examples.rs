@@ -0,0 +1,53 @@
+use crate::{
+    cache::write_cache_file, fetcher::extract_puzzle_text, markdown::unescape_html, AocError,
+};
+
+lazy_static::lazy_static! {
+    static ref CODE_BLOCK_REGEX: regex::Regex = regex::RegexBuilder::new(r#"<pre><code>(.*?)</code></pre>"#)
+        .dot_matches_new_line(true)
+        .build()
+        .unwrap();
+    static ref TAG_REGEX: regex::Regex = regex::Regex::new(r#"<[^>]*>"#).unwrap();
+}
+
+pub(crate) fn example_file_name(n: usize) -> String {
+    format!("example-{}", n)
+}
+
+/// Extract the example blocks (`<pre><code>`) from the puzzle descriptions of a puzzle page
+pub fn extract_examples(page: &str) -> Vec<String> {
+    let text = extract_puzzle_text(page);
+    CODE_BLOCK_REGEX
+        .captures_iter(&text)
+        .map(|c| unescape_html(&TAG_REGEX.replace_all(c.get(1).unwrap().as_str(), "")))
+        .collect()
+}
+
+/// Store the examples of a puzzle page as `example-N` files, numbered from 1
+pub(crate) fn store_examples(day: u32, year: u32, page: &str) -> Result<Vec<String>, AocError> {
+    let examples = extract_examples(page);
+    for (n, example) in (1..).zip(&examples) {
+        write_cache_file(day, year, &example_file_name(n), example)?;
+    }
+    Ok(examples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_examples() {
+        let page = r#"<article class="day-desc"><p>For example:</p>
+<pre><code>1-3 &lt;a&gt;
+<em>4</em>-5
+</code></pre>
+<p>The answer is <code>7</code>.</p></article>
+<pre><code>outside article</code></pre>
+<article class="day-desc"><pre><code>A &amp; B</code></pre></article>"#;
+        assert_eq!(
+            extract_examples(page),
+            vec!["1-3 <a>\n4-5\n".to_string(), "A & B".to_string()]
+        );
+    }
+}
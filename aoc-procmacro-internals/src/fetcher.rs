@@ -1,99 +1,224 @@
-use reqwest::blocking::Client;
-use std::{env, fs, sync::Mutex};
+use crate::{
+    cache::{
+        needs_text_refresh, read_cache_file, store_puzzle_text, validate_cached, write_cache_file,
+    },
+    clock::get_clock,
+    config::{get_config_or_default, search_dirs},
+    examples::{example_file_name, store_examples},
+    ledger::record_page_answers,
+    markdown::html_to_markdown,
+    release::ensure_released,
+    session::{get_session, is_session_rejected},
+    throttle::throttle,
+    transport::{get_transport, HttpMethod, HttpRequest, HttpResponse},
+    AocError,
+};
+use std::{env, io, path::PathBuf, sync::Mutex, time::Duration};
 
-pub const AOC_SESSION_ENV_VAR: &str = "AOC_SESSION";
+pub const AOC_BASE_URL_ENV_VAR: &str = "AOC_BASE_URL";
+pub const AOC_OFFLINE_ENV_VAR: &str = "AOC_OFFLINE";
+pub const AOC_DATA_DIR_ENV_VAR: &str = "AOC_DATA_DIR";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com/";
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_BACKOFF: Duration = Duration::from_secs(2);
 
 lazy_static::lazy_static! {
-    static ref AOC_SESSION: Mutex<Option<String>> = Mutex::new(env::var_os(AOC_SESSION_ENV_VAR).and_then(|v| v.into_string().ok()));
+    static ref AOC_BASE_URL: Mutex<String> = Mutex::new(
+        env::var_os(AOC_BASE_URL_ENV_VAR)
+            .and_then(|v| v.into_string().ok())
+            .or_else(|| get_config_or_default().base_url.clone())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+    );
+    static ref AOC_DATA_DIR: Mutex<Option<PathBuf>> = Mutex::new(
+        env::var_os(AOC_DATA_DIR_ENV_VAR)
+            .map(PathBuf::from)
+            .or_else(|| get_config_or_default().data_dir.clone())
+    );
+    static ref AOC_OFFLINE: Mutex<bool> = Mutex::new(match env::var(AOC_OFFLINE_ENV_VAR) {
+        Ok(v) => !matches!(v.as_str(), "" | "0" | "false"),
+        Err(_) => get_config_or_default().offline.unwrap_or(false),
+    });
 }
 
-/// Set the AOC session to use when downloading data.
-pub fn set_session(session_id: String) {
-    let mut session = AOC_SESSION.lock().unwrap();
-    *session = Some(session_id);
+/// Set the base url of the AOC website, useful to point requests to a local stub server.
+pub fn set_base_url(base_url: String) {
+    let mut url = AOC_BASE_URL.lock().unwrap();
+    *url = base_url;
+}
+
+/// Enable or disable offline mode, when offline only cached data is used and AOC is never contacted.
+pub fn set_offline(offline: bool) {
+    let mut current = AOC_OFFLINE.lock().unwrap();
+    *current = offline;
+}
+
+pub fn is_offline() -> bool {
+    *AOC_OFFLINE.lock().unwrap()
+}
+
+/// Set the `aoc_data` directory, instead of searching it from the current directory.
+pub fn set_data_dir(data_dir: PathBuf) {
+    let mut dir = AOC_DATA_DIR.lock().unwrap();
+    *dir = Some(data_dir);
+}
+
+pub(crate) fn get_url(path: &str) -> String {
+    let base_url = AOC_BASE_URL.lock().unwrap();
+    format!("{}/{}", base_url.trim_end_matches('/'), path)
 }
 
 pub enum AocDataType {
     Text,
     Input,
+    /// Example input from the puzzle text, numbered from 1
+    Example(usize),
 }
 
 impl AocDataType {
-    fn fetch(&self, day: u32, year: u32) -> Result<String, String> {
+    pub(crate) fn fetch(&self, day: u32, year: u32) -> Result<String, AocError> {
         match self {
-            AocDataType::Text => {
-                let path = format!("{}/day/{}", year, day);
-                let output = fetch_from_aoc(&path)?;
-                let output = process_puzzle_html(output)?;
-                Ok(output)
-            }
+            AocDataType::Text => fetch_puzzle_text(day, year).map(|(text, _)| text),
             AocDataType::Input => {
+                ensure_released(day, year)?;
                 let path = format!("{}/day/{}/input", year, day);
                 fetch_from_aoc(&path)
             }
+            AocDataType::Example(n) => {
+                ensure_released(day, year)?;
+                let path = format!("{}/day/{}", year, day);
+                let output = fetch_from_aoc(&path)?;
+                record_page_answers(day, year, &output)?;
+                let examples = store_examples(day, year, &output)?;
+                n.checked_sub(1)
+                    .and_then(|i| examples.into_iter().nth(i))
+                    .ok_or_else(|| AocError::NotFound(format!("puzzle text has no example {}", n)))
+            }
         }
     }
 
-    fn file_name(&self) -> String {
+    pub(crate) fn file_name(&self) -> String {
         match self {
             AocDataType::Text => "text.md".into(),
             AocDataType::Input => "input".into(),
+            AocDataType::Example(n) => example_file_name(*n),
         }
     }
 }
 
-pub fn get_aoc_data(data_type: AocDataType, day: u32, year: u32) -> Result<String, String> {
-    let current_dir = env::current_dir().expect("Current directory");
-    let mut dir = Some(current_dir.as_path());
-    let data_dir = loop {
-        if let Some(d) = dir {
-            if d.join("aoc_data").exists() {
-                break Some(d.join("aoc_data"));
-            }
-            dir = d.parent();
-        } else {
-            break None;
+/// Download the puzzle page, returning the puzzle text and the number of parts the page has unlocked
+pub(crate) fn fetch_puzzle_text(day: u32, year: u32) -> Result<(String, usize), AocError> {
+    ensure_released(day, year)?;
+    let path = format!("{}/day/{}", year, day);
+    let page = fetch_from_aoc(&path)?;
+    let ledger = record_page_answers(day, year, &page)?;
+    store_examples(day, year, &page)?;
+    // A page showing the part 1 answer has unlocked part 2, even if it somehow does not describe it
+    let answered = ledger.correct_answer(1).is_some() as usize;
+    let parts = ARTICLE_REGEX.find_iter(&page).count().max(answered + 1);
+    Ok((process_puzzle_html(page)?, parts))
+}
+
+pub fn get_aoc_data(data_type: AocDataType, day: u32, year: u32) -> Result<String, AocError> {
+    let file_name = data_type.file_name();
+    let Some(data) = read_cache_file(day, year, &file_name)? else {
+        if let AocDataType::Text = data_type {
+            return store_puzzle_text(day, year);
         }
-    }
-    .unwrap_or_else(|| current_dir.join("aoc_data"));
-    let data_dir = data_dir.join(year.to_string()).join(day.to_string());
-    if !data_dir.exists() {
-        fs::create_dir_all(&data_dir).expect("Create data directory");
-    }
-    let file_name = data_dir.join(data_type.file_name());
-    if !file_name.exists() {
         let data = data_type.fetch(day, year)?;
-        fs::write(&file_name, data.clone()).expect("Write data to file");
+        write_cache_file(day, year, &file_name, &data)?;
         return Ok(data);
+    };
+    validate_cached(day, year, &file_name, &data)?;
+    if let AocDataType::Text = data_type {
+        if needs_text_refresh(day, year, &data) {
+            // The cached text remains usable when the part 2 description can not be fetched yet
+            return Ok(store_puzzle_text(day, year).unwrap_or(data));
+        }
+    }
+    Ok(data)
+}
+
+/// Locate the `aoc_data` directory for a puzzle day, it is only created when data is written to it
+pub(crate) fn get_data_dir(day: u32, year: u32) -> Result<PathBuf, AocError> {
+    Ok(get_data_root()?
+        .join(year.to_string())
+        .join(day.to_string()))
+}
+
+/// Locate the `aoc_data` directory
+///
+/// Uses the configured directory if any, otherwise walks up from the crate or current directory.
+pub(crate) fn get_data_root() -> Result<PathBuf, AocError> {
+    if let Some(data_dir) = AOC_DATA_DIR.lock().unwrap().clone() {
+        return Ok(data_dir);
+    }
+    let search_dirs = search_dirs();
+    search_dirs
+        .iter()
+        .flat_map(|dir| dir.ancestors())
+        .map(|dir| dir.join("aoc_data"))
+        .find(|dir| dir.exists())
+        .or_else(|| search_dirs.first().map(|dir| dir.join("aoc_data")))
+        .ok_or_else(|| {
+            AocError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no crate or current directory to find aoc_data in",
+            ))
+        })
+}
+
+pub(crate) fn fetch_from_aoc(path: &str) -> Result<String, AocError> {
+    let response = send_to_aoc(HttpRequest::get(get_url(path)))?;
+    if !response.is_success() {
+        return Err(AocError::HttpStatus {
+            status: response.status,
+            body: response.body,
+        });
     }
-    fs::read_to_string(&file_name).map_err(|e| e.to_string())
+    Ok(response.body)
 }
 
-fn fetch_from_aoc(path: &str) -> Result<String, String> {
-    let aoc_session = AOC_SESSION.lock().unwrap().clone();
-    let aoc_session = if let Some(session_id) = aoc_session {
+/// Sends an authenticated and tagged request to the AOC website.
+///
+/// Requests are throttled. GET requests are retried with a backoff when the server responds with an error, other
+/// requests are sent once: resending an answer which was accepted before the error would lose its verdict.
+pub(crate) fn send_to_aoc(request: HttpRequest) -> Result<HttpResponse, AocError> {
+    if is_offline() {
+        return Err(AocError::Offline(request.path().to_string()));
+    }
+    let aoc_session = if let Some(session_id) = get_session() {
         session_id
     } else {
-        return Err("Cannot download input, AOC_SESSION unavailable".to_string());
+        return Err(AocError::MissingSession);
     };
 
     let repo_url = env!("CARGO_PKG_REPOSITORY");
     let authors = env!("CARGO_PKG_AUTHORS");
 
-    let url = format!("https://adventofcode.com/{}", path);
-    let client = Client::new();
-    let response = client
-        .get(url)
-        .header("cookie", format!("session={}", aoc_session))
-        .header("User-Agent", format!("{} by {}", repo_url, authors))
-        .send()
-        .map_err(|e| e.to_string())?;
-    let status = response.status();
-    let text: String = response.text().map_err(|e| e.to_string())?;
-    if !status.is_success() {
-        return Err(format!("Downloading input failed: {}; {}", status, text));
+    let request = request
+        .with_header("cookie", format!("session={}", aoc_session))
+        .with_header("User-Agent", format!("{} by {}", repo_url, authors));
+    let transport = get_transport();
+    let mut attempt = 1;
+    loop {
+        throttle()?;
+        let response = transport.send(&request)?;
+        if response.status == 429 {
+            return Err(AocError::RateLimited {
+                retry_after: response.header("retry-after").map(String::from),
+            });
+        }
+        if is_session_rejected(&response) {
+            return Err(AocError::SessionExpired);
+        }
+        if response.is_server_error() && request.method == HttpMethod::Get && attempt < MAX_ATTEMPTS
+        {
+            get_clock().sleep(RETRY_BACKOFF * 2u32.pow(attempt - 1));
+            attempt += 1;
+            continue;
+        }
+        return Ok(response);
     }
-    Ok(text)
 }
 
 lazy_static::lazy_static! {
@@ -102,26 +227,219 @@ lazy_static::lazy_static! {
         .dot_matches_new_line(true)
         .build()
         .unwrap();
-    static ref LINE_ENDINGS: regex::Regex = regex::Regex::new(r#"</p>|</pre>"#).unwrap();
-    static ref STRONG_BLOCK: regex::Regex = regex::Regex::new(r#"<code><em>([^<]*)</em></code>"#).unwrap();
 }
 
-pub fn process_puzzle_html(text: String) -> Result<String, String> {
-    use html2md_rs::to_md::safe_from_html_to_md;
-
-    let text = extract_puzzle_text(text)?;
-    let text = LINE_ENDINGS.replace_all(&text, "\n$0").to_string();
-    let text = STRONG_BLOCK
-        .replace_all(&text, "<strong>$1</strong>")
-        .to_string();
-    let text = safe_from_html_to_md(format!("<div>{}</div>", text)).map_err(|e| e.to_string())?;
-    Ok(text)
+/// Convert the puzzle descriptions of a puzzle page to Markdown, each part starts with its own heading
+pub fn process_puzzle_html(text: String) -> Result<String, AocError> {
+    let text = extract_puzzle_text(&text);
+    if text.is_empty() {
+        return Err(AocError::Parse(
+            "page contains no puzzle description".to_string(),
+        ));
+    }
+    Ok(html_to_markdown(&text))
 }
 
-fn extract_puzzle_text(text: String) -> Result<String, String> {
-    Ok(ARTICLE_REGEX
-        .captures_iter(&text)
+pub(crate) fn extract_puzzle_text(text: &str) -> String {
+    ARTICLE_REGEX
+        .captures_iter(text)
         .map(|c| c.get(1).unwrap().as_str().to_string())
         .collect::<Vec<String>>()
-        .join("\n***\n"))
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, sync::Arc, time::SystemTime};
+
+    use super::*;
+    use crate::{
+        check_cache, is_cached, set_clock, test_utils::TestEnv, AnswerLedger, Clock, FixedClock,
+        FixtureTransport,
+    };
+
+    const PUZZLE_PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Count the <em>numbers</em>:</p>
+<pre><code>1
+2
+</code></pre>
+</article>
+<p>Your puzzle answer was <code>3</code>.</p>
+</main></body></html>"#;
+
+    #[test]
+    fn test_get_input() {
+        let env = TestEnv::new(
+            "input",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/day/1/input",
+                HttpResponse::new(200, "1\n2\n"),
+            ),
+        );
+        assert_eq!(
+            get_aoc_data(AocDataType::Input, 1, 2025).unwrap(),
+            "1\n2\n".to_string()
+        );
+        assert_eq!(
+            get_aoc_data(AocDataType::Input, 1, 2025).unwrap(),
+            "1\n2\n".to_string()
+        );
+
+        let requests = env.transport.requests();
+        assert_eq!(requests.len(), 1, "second call should be cached");
+        assert!(requests[0]
+            .headers
+            .contains(&("cookie".to_string(), "session=test-session".to_string())));
+        assert!(env.data_dir.join("2025/1/input").exists());
+    }
+
+    #[test]
+    fn test_get_input_failure_not_cached() {
+        let env = TestEnv::new("failure", FixtureTransport::new());
+        let result = get_aoc_data(AocDataType::Input, 2, 2025);
+        assert!(matches!(
+            result,
+            Err(AocError::HttpStatus { status: 404, .. })
+        ));
+        assert!(!env.data_dir.join("2025/2/input").exists());
+    }
+
+    #[test]
+    fn test_get_text() {
+        let _env = TestEnv::new(
+            "text",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/day/1",
+                HttpResponse::new(200, PUZZLE_PAGE),
+            ),
+        );
+        let text = get_aoc_data(AocDataType::Text, 1, 2025).unwrap();
+        assert!(text.contains("--- Day 1: Test ---"));
+        assert!(text.contains("numbers"));
+        assert_eq!(
+            AnswerLedger::load(1, 2025).unwrap().correct_answer(1),
+            Some("3")
+        );
+    }
+
+    #[test]
+    fn test_get_example() {
+        let env = TestEnv::new(
+            "example",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/day/1",
+                HttpResponse::new(200, PUZZLE_PAGE),
+            ),
+        );
+        assert_eq!(
+            get_aoc_data(AocDataType::Example(1), 1, 2025).unwrap(),
+            "1\n2\n".to_string()
+        );
+        assert!(get_aoc_data(AocDataType::Example(2), 1, 2025).is_err());
+
+        // Examples are stored when fetching the text
+        fs::remove_file(env.data_dir.join("2025/1/example-1")).unwrap();
+        get_aoc_data(AocDataType::Text, 1, 2025).unwrap();
+        assert!(env.data_dir.join("2025/1/example-1").exists());
+    }
+
+    #[test]
+    fn test_offline() {
+        let env = TestEnv::new(
+            "offline",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/day/1/input",
+                HttpResponse::new(200, "1\n2\n"),
+            ),
+        );
+        set_offline(true);
+        let result = get_aoc_data(AocDataType::Input, 1, 2025);
+        set_offline(false);
+        assert!(matches!(result, Err(AocError::Offline(_))));
+        assert!(env.transport.requests().is_empty());
+    }
+
+    #[test]
+    fn test_rate_limited() {
+        let _env = TestEnv::new(
+            "rate-limited",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/day/1/input",
+                HttpResponse::new(429, "Too many requests"),
+            ),
+        );
+        let result = get_aoc_data(AocDataType::Input, 1, 2025);
+        assert!(matches!(result, Err(AocError::RateLimited { .. })));
+    }
+
+    #[test]
+    fn test_invalid_data_dir() {
+        let env = TestEnv::new(
+            "invalid-data-dir",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/day/1/input",
+                HttpResponse::new(200, "1\n2\n"),
+            ),
+        );
+        // Reading a day which is not cached leaves the data directory alone
+        assert!(!is_cached(&AocDataType::Input, 1, 2025));
+        assert!(check_cache(1, 2025).unwrap().is_empty());
+        assert!(!env.data_dir.exists());
+
+        fs::create_dir_all(env.data_dir.parent().unwrap()).unwrap();
+        fs::write(&env.data_dir, "not a directory").unwrap();
+        let result = get_aoc_data(AocDataType::Input, 1, 2025);
+        fs::remove_file(&env.data_dir).unwrap();
+        assert!(matches!(result, Err(AocError::Io(_))));
+    }
+
+    #[test]
+    fn test_retry_server_error() {
+        let env = TestEnv::new(
+            "retry",
+            FixtureTransport::new()
+                .with_response(
+                    HttpMethod::Get,
+                    "2025/day/1/input",
+                    HttpResponse::new(503, "Service Unavailable"),
+                )
+                .with_response(
+                    HttpMethod::Post,
+                    "2025/day/1/answer",
+                    HttpResponse::new(502, "Bad Gateway"),
+                ),
+        );
+        let start = SystemTime::now();
+        let clock = Arc::new(FixedClock::new(start));
+        set_clock(clock.clone());
+
+        let result = get_aoc_data(AocDataType::Input, 1, 2025);
+        assert!(matches!(
+            result,
+            Err(AocError::HttpStatus { status: 503, .. })
+        ));
+        assert_eq!(env.transport.requests().len(), 3);
+        assert_eq!(
+            clock.now().duration_since(start).unwrap(),
+            RETRY_BACKOFF * 3,
+            "backoff of 2 and 4 seconds"
+        );
+
+        let response = send_to_aoc(HttpRequest::post(get_url("2025/day/1/answer"))).unwrap();
+        assert_eq!(response.status, 502);
+        assert_eq!(env.transport.requests().len(), 4, "answers are not resent");
+    }
+
+    #[test]
+    fn test_process_puzzle_html() {
+        let text = process_puzzle_html(PUZZLE_PAGE.to_string()).unwrap();
+        assert!(text.contains("--- Day 1: Test ---"));
+        assert!(!text.contains("Your puzzle answer was"));
+    }
 }
@@ -0,0 +1,215 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    clock::get_clock,
+    fetcher::{fetch_from_aoc, get_data_root, is_offline},
+    release::release_time,
+    AocError,
+};
+
+/// AOC asks not to request a private leaderboard more than once every 15 minutes
+pub const LEADERBOARD_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// A private leaderboard, as returned by its JSON endpoint
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: BTreeMap<String, LeaderboardMember>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LeaderboardMember {
+    pub id: u64,
+    /// Name of the member, anonymous users have none
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// Stars collected per day and part
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u32, StarCompletion>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StarCompletion {
+    /// Unix timestamp of the moment the star was collected
+    pub get_star_ts: u64,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, AocError> {
+        serde_json::from_str(json)
+            .map_err(|e| AocError::Parse(format!("invalid leaderboard: {}", e)))
+    }
+
+    /// Members ordered by local score, then by number of stars
+    pub fn ranking(&self) -> Vec<&LeaderboardMember> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+}
+
+impl LeaderboardMember {
+    /// Name as AOC shows it, including for anonymous users
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Number of stars collected on a day
+    pub fn day_stars(&self, day: u32) -> usize {
+        self.completion_day_level
+            .get(&day)
+            .map_or(0, |parts| parts.len())
+    }
+
+    /// Time from the release of the puzzle until the star of a part was collected
+    pub fn completion_time(&self, day: u32, part: u32, year: u32) -> Option<Duration> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        let collected = UNIX_EPOCH + Duration::from_secs(star.get_star_ts);
+        collected.duration_since(release_time(day, year).ok()?).ok()
+    }
+}
+
+/// Get a private leaderboard, from the cache while it was fetched less than 15 minutes ago
+///
+/// Cached as `aoc_data/<year>/leaderboard-<id>.json`, together with the time it was fetched.
+/// In offline mode the cache is used regardless of its age.
+pub fn get_leaderboard(id: u64, year: u32) -> Result<Leaderboard, AocError> {
+    let path = leaderboard_path(id, year)?;
+    let now = get_clock().now();
+    if let Some(cached) = read_cached(&path) {
+        let fetched_at = UNIX_EPOCH + Duration::from_secs(cached.fetched_at);
+        let age = now.duration_since(fetched_at).unwrap_or_default();
+        if is_offline() || age < LEADERBOARD_REFRESH_INTERVAL {
+            return Ok(cached.leaderboard);
+        }
+    }
+    let json = fetch_from_aoc(&format!("{}/leaderboard/private/view/{}.json", year, id))?;
+    // Parse before caching, so an error page is never stored
+    let leaderboard = Leaderboard::parse(&json)?;
+    let value: serde_json::Value = serde_json::from_str(&json)
+        .map_err(|e| AocError::Parse(format!("invalid leaderboard: {}", e)))?;
+    let fetched_at = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let cached = serde_json::json!({ "fetched_at": fetched_at, "leaderboard": value });
+    fs::create_dir_all(path.parent().expect("Year directory"))?;
+    fs::write(&path, cached.to_string())?;
+    Ok(leaderboard)
+}
+
+/// A leaderboard as cached, with the time it was fetched in seconds since the Unix epoch
+#[derive(Deserialize)]
+struct CachedLeaderboard {
+    fetched_at: u64,
+    leaderboard: Leaderboard,
+}
+
+/// The cached leaderboard, `None` when it is missing or in an older format
+fn read_cached(path: &Path) -> Option<CachedLeaderboard> {
+    let json = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+fn leaderboard_path(id: u64, year: u32) -> Result<PathBuf, AocError> {
+    Ok(get_data_root()?
+        .join(year.to_string())
+        .join(format!("leaderboard-{}.json", id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        set_clock, set_offline, test_utils::TestEnv, Clock, FixedClock, FixtureTransport,
+        HttpMethod, HttpResponse,
+    };
+
+    // Day 1 of 2025 released at 1764565200
+    const LEADERBOARD: &str = include_str!("../test_data/leaderboard/private.json");
+
+    #[test]
+    fn test_parse_leaderboard() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let ranking = leaderboard.ranking();
+        assert_eq!(
+            ranking.iter().map(|m| m.display_name()).collect::<Vec<_>>(),
+            vec!["Alice", "(anonymous user #2)", "Carol"]
+        );
+        assert_eq!(ranking[0].day_stars(1), 2);
+        assert_eq!(ranking[0].day_stars(2), 1);
+        assert_eq!(ranking[2].day_stars(1), 0);
+        assert_eq!(
+            ranking[0].completion_time(1, 2, 2025),
+            Some(Duration::from_secs(1200))
+        );
+        assert_eq!(ranking[0].completion_time(2, 2, 2025), None);
+        assert!(Leaderboard::parse("<html>Please log in</html>").is_err());
+    }
+
+    #[test]
+    fn test_leaderboard_refresh_interval() {
+        let env = TestEnv::new(
+            "leaderboard",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/leaderboard/private/view/1.json",
+                HttpResponse::new(200, LEADERBOARD),
+            ),
+        );
+        // Far from the time the cache file is written, the age only depends on the clock
+        let clock = Arc::new(FixedClock::new(
+            UNIX_EPOCH + Duration::from_secs(1764652000),
+        ));
+        set_clock(clock.clone());
+
+        get_leaderboard(1, 2025).unwrap();
+        let cached = fs::read_to_string(env.data_dir.join("2025/leaderboard-1.json")).unwrap();
+        assert!(cached.contains(r#""fetched_at":1764652000"#));
+        clock.sleep(Duration::from_secs(10 * 60));
+        get_leaderboard(1, 2025).unwrap();
+        assert_eq!(env.transport.requests().len(), 1, "cached for 15 minutes");
+
+        clock.sleep(Duration::from_secs(6 * 60));
+        set_offline(true);
+        get_leaderboard(1, 2025).unwrap();
+        set_offline(false);
+        assert_eq!(env.transport.requests().len(), 1, "offline uses the cache");
+
+        get_leaderboard(1, 2025).unwrap();
+        assert_eq!(
+            env.transport.requests().len(),
+            2,
+            "refreshed after 15 minutes"
+        );
+    }
+
+    #[test]
+    fn test_leaderboard_error_not_cached() {
+        let env = TestEnv::new(
+            "leaderboard-error",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/leaderboard/private/view/1.json",
+                HttpResponse::new(200, "<!DOCTYPE html><html>Not a member</html>"),
+            ),
+        );
+        assert!(matches!(get_leaderboard(1, 2025), Err(AocError::Parse(_))));
+        assert!(!env.data_dir.join("2025/leaderboard-1.json").exists());
+    }
+}
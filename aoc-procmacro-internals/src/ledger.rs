@@ -0,0 +1,325 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    fetcher::{fetch_from_aoc, get_data_dir},
+    AocError, SubmitOutcome,
+};
+
+const LEDGER_FILE_NAME: &str = "answers";
+
+lazy_static::lazy_static! {
+    static ref PUZZLE_ANSWER_REGEX: regex::Regex = regex::Regex::new(r#"Your puzzle answer was <code>([^<]*)</code>"#).unwrap();
+}
+
+/// Verdict AOC gave on a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    /// Convert a submit outcome into a verdict, outcomes which say nothing about the answer are ignored
+    pub fn from_outcome(outcome: &SubmitOutcome) -> Option<Self> {
+        match outcome {
+            SubmitOutcome::Correct => Some(Verdict::Correct),
+            SubmitOutcome::Wrong => Some(Verdict::Wrong),
+            SubmitOutcome::TooHigh => Some(Verdict::TooHigh),
+            SubmitOutcome::TooLow => Some(Verdict::TooLow),
+            SubmitOutcome::RateLimited(_)
+            | SubmitOutcome::WrongLevel
+            | SubmitOutcome::AlreadyVerified => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "correct" => Some(Verdict::Correct),
+            "wrong" => Some(Verdict::Wrong),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            _ => None,
+        }
+    }
+}
+
+/// What is known about an answer, based on earlier submissions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnswerStatus {
+    Verified,
+    KnownWrong(String),
+    Unknown,
+}
+
+impl std::fmt::Display for AnswerStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswerStatus::Verified => write!(f, "verified"),
+            AnswerStatus::KnownWrong(reason) => write!(f, "known wrong, {}", reason),
+            AnswerStatus::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerEntry {
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Record of all answers submitted for a puzzle day
+///
+/// Stored as `aoc_data/<year>/<day>/answers`, one tab separated `part verdict answer` line per submission.
+#[derive(Debug)]
+pub struct AnswerLedger {
+    path: PathBuf,
+    entries: Vec<LedgerEntry>,
+}
+
+impl AnswerLedger {
+    /// Load the ledger of a puzzle day, an empty ledger is returned when nothing was submitted yet
+    pub fn load(day: u32, year: u32) -> Result<Self, AocError> {
+        Self::open(get_data_dir(day, year)?.join(LEDGER_FILE_NAME))
+    }
+
+    pub(crate) fn open(path: PathBuf) -> Result<Self, AocError> {
+        let entries = if path.exists() {
+            fs::read_to_string(&path)?
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(parse_entry)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|reason| AocError::CacheCorrupt {
+                    path: path.clone(),
+                    reason,
+                })?
+        } else {
+            vec![]
+        };
+        Ok(Self { path, entries })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn entries(&self) -> &[LedgerEntry] {
+        &self.entries
+    }
+
+    /// The accepted answer for a part, if any
+    pub fn correct_answer(&self, part: u8) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.part == part && e.verdict == Verdict::Correct)
+            .map(|e| e.answer.as_str())
+    }
+
+    /// Smallest answer known to be too high and largest answer known to be too low
+    pub fn bounds(&self, part: u8) -> (Option<i128>, Option<i128>) {
+        let numeric = |verdict: Verdict| {
+            self.entries
+                .iter()
+                .filter(move |e| e.part == part && e.verdict == verdict)
+                .filter_map(|e| e.answer.parse::<i128>().ok())
+        };
+        (
+            numeric(Verdict::TooLow).max(),
+            numeric(Verdict::TooHigh).min(),
+        )
+    }
+
+    /// Check an answer against all earlier submissions of the part
+    pub fn check(&self, part: u8, answer: &str) -> AnswerStatus {
+        if let Some(correct) = self.correct_answer(part) {
+            return if correct == answer {
+                AnswerStatus::Verified
+            } else {
+                AnswerStatus::KnownWrong(format!("correct answer is {}", correct))
+            };
+        }
+        if let Some(entry) = self
+            .entries
+            .iter()
+            .find(|e| e.part == part && e.answer == answer)
+        {
+            return AnswerStatus::KnownWrong(format!(
+                "already submitted, {}",
+                entry.verdict.as_str()
+            ));
+        }
+        if let Ok(value) = answer.parse::<i128>() {
+            let (too_low, too_high) = self.bounds(part);
+            if let Some(low) = too_low.filter(|low| value <= *low) {
+                return AnswerStatus::KnownWrong(format!("too low, must be above {}", low));
+            }
+            if let Some(high) = too_high.filter(|high| value >= *high) {
+                return AnswerStatus::KnownWrong(format!("too high, must be below {}", high));
+            }
+        }
+        AnswerStatus::Unknown
+    }
+
+    /// Store the outcome of a submission, outcomes without a verdict are not recorded
+    pub fn record(
+        &mut self,
+        part: u8,
+        answer: &str,
+        outcome: &SubmitOutcome,
+    ) -> Result<(), AocError> {
+        let Some(verdict) = Verdict::from_outcome(outcome) else {
+            return Ok(());
+        };
+        self.entries.push(LedgerEntry {
+            part,
+            verdict,
+            answer: answer.to_string(),
+        });
+        self.save()
+    }
+
+    /// Store an answer which is known to be correct, unless the part already has a correct answer
+    pub fn record_correct(&mut self, part: u8, answer: &str) -> Result<(), AocError> {
+        if self.correct_answer(part).is_some() {
+            return Ok(());
+        }
+        self.record(part, answer, &SubmitOutcome::Correct)
+    }
+
+    pub(crate) fn save(&self) -> Result<(), AocError> {
+        let content = self
+            .entries
+            .iter()
+            .map(|e| format!("{}\t{}\t{}\n", e.part, e.verdict.as_str(), e.answer))
+            .collect::<String>();
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+/// Download the puzzle page and store the answers listed on it as correct answers
+pub fn fetch_answers(day: u32, year: u32) -> Result<AnswerLedger, AocError> {
+    let page = fetch_from_aoc(&format!("{}/day/{}", year, day))?;
+    record_page_answers(day, year, &page)
+}
+
+/// Store the "Your puzzle answer was" answers of a puzzle page as correct answers
+pub(crate) fn record_page_answers(
+    day: u32,
+    year: u32,
+    page: &str,
+) -> Result<AnswerLedger, AocError> {
+    let mut ledger = AnswerLedger::load(day, year)?;
+    for (part, answer) in (1..).zip(scrape_answers(page)) {
+        ledger.record_correct(part, answer)?;
+    }
+    Ok(ledger)
+}
+
+fn scrape_answers(page: &str) -> impl Iterator<Item = &str> {
+    PUZZLE_ANSWER_REGEX
+        .captures_iter(page)
+        .map(|c| c.get(1).unwrap().as_str())
+}
+
+fn parse_entry(line: &str) -> Result<LedgerEntry, String> {
+    let invalid = || format!("invalid answer ledger entry: {}", line);
+    let mut fields = line.splitn(3, '\t');
+    let part = fields
+        .next()
+        .and_then(|p| p.parse().ok())
+        .ok_or_else(invalid)?;
+    let verdict = fields.next().and_then(Verdict::parse).ok_or_else(invalid)?;
+    let answer = fields.next().ok_or_else(invalid)?.to_string();
+    Ok(LedgerEntry {
+        part,
+        verdict,
+        answer,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_ledger(name: &str) -> AnswerLedger {
+        let path = std::env::temp_dir().join(format!("aoc-ledger-{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        AnswerLedger::open(path).unwrap()
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut ledger = create_ledger("bounds");
+        ledger.record(1, "100", &SubmitOutcome::TooHigh).unwrap();
+        ledger.record(1, "20", &SubmitOutcome::TooLow).unwrap();
+        ledger.record(1, "80", &SubmitOutcome::TooHigh).unwrap();
+        ledger.record(1, "42", &SubmitOutcome::Wrong).unwrap();
+        ledger
+            .record(1, "50", &SubmitOutcome::RateLimited(Default::default()))
+            .unwrap();
+
+        assert_eq!(ledger.bounds(1), (Some(20), Some(80)));
+        assert_eq!(ledger.check(1, "50"), AnswerStatus::Unknown);
+        assert_eq!(
+            ledger.check(1, "90"),
+            AnswerStatus::KnownWrong("too high, must be below 80".to_string())
+        );
+        assert_eq!(
+            ledger.check(1, "15"),
+            AnswerStatus::KnownWrong("too low, must be above 20".to_string())
+        );
+        assert_eq!(
+            ledger.check(1, "42"),
+            AnswerStatus::KnownWrong("already submitted, wrong".to_string())
+        );
+        assert_eq!(ledger.check(2, "90"), AnswerStatus::Unknown);
+        let _ = fs::remove_file(&ledger.path);
+    }
+
+    #[test]
+    fn test_persisted() {
+        let mut ledger = create_ledger("persisted");
+        ledger.record(1, "abc", &SubmitOutcome::Wrong).unwrap();
+        ledger.record(1, "def", &SubmitOutcome::Correct).unwrap();
+
+        let ledger = AnswerLedger::open(ledger.path.clone()).unwrap();
+        assert_eq!(ledger.entries().len(), 2);
+        assert_eq!(ledger.correct_answer(1), Some("def"));
+        assert_eq!(ledger.check(1, "def"), AnswerStatus::Verified);
+        assert_eq!(
+            ledger.check(1, "ghi"),
+            AnswerStatus::KnownWrong("correct answer is def".to_string())
+        );
+        let _ = fs::remove_file(&ledger.path);
+    }
+
+    #[test]
+    fn test_scrape_answers() {
+        let page = r#"<article class="day-desc"><p>...</p></article>
+<p>Your puzzle answer was <code>1234</code>.</p><article class="day-desc"><p>...</p></article>
+<p>Your puzzle answer was <code>abc</code>.</p><p class="day-success">Both parts of this puzzle are complete!</p>"#;
+        assert_eq!(
+            scrape_answers(page).collect::<Vec<_>>(),
+            vec!["1234", "abc"]
+        );
+    }
+}
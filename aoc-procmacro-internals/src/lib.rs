@@ -1,5 +1,59 @@
+mod cache;
+pub use cache::*;
+mod clock;
+pub use clock::*;
+mod config;
+pub use config::*;
+mod encryption;
+pub use encryption::*;
+mod error;
+pub use error::*;
+mod examples;
+pub use examples::*;
 mod fetcher;
 pub use fetcher::*;
+mod leaderboard;
+pub use leaderboard::*;
+mod ledger;
+pub use ledger::*;
+mod markdown;
+pub use markdown::*;
+mod release;
+pub use release::*;
+mod session;
+pub use session::*;
+mod submit;
+pub use submit::*;
+mod throttle;
+pub use throttle::*;
+mod transport;
+pub use transport::*;
+#[cfg(test)]
+mod test_utils;
 pub mod public {
-    pub use super::fetcher::{get_aoc_data, set_session, AocDataType};
+    pub use super::cache::{
+        cached_puzzle_text, check_cache, encrypt_cache, is_cached, refetch, update_puzzle_text,
+        CacheIssue,
+    };
+    pub use super::clock::{set_clock, Clock, FixedClock, SystemClock};
+    pub use super::config::{get_config, AocConfig};
+    pub use super::encryption::set_cache_passphrase;
+    pub use super::error::AocError;
+    pub use super::fetcher::{
+        get_aoc_data, is_offline, set_base_url, set_data_dir, set_offline, AocDataType,
+    };
+    pub use super::leaderboard::{
+        get_leaderboard, Leaderboard, LeaderboardMember, StarCompletion,
+        LEADERBOARD_REFRESH_INTERVAL,
+    };
+    pub use super::ledger::{fetch_answers, AnswerLedger, AnswerStatus};
+    pub use super::release::{
+        format_countdown, puzzle_days, release_time, time_until_release, wait_for_release,
+    };
+    pub use super::session::{check_session, set_session, user_session_file, SessionInfo};
+    pub use super::submit::SubmitOutcome;
+    pub use super::throttle::set_throttle_interval;
+    pub use super::transport::{
+        set_transport, FixtureTransport, HttpMethod, HttpRequest, HttpResponse, Transport,
+    };
 }
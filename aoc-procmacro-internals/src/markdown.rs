@@ -0,0 +1,512 @@
+const AOC_ORIGIN: &str = "https://adventofcode.com";
+
+lazy_static::lazy_static! {
+    static ref ENTITY_REGEX: regex::Regex = regex::Regex::new(r#"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);"#).unwrap();
+}
+
+/// Convert the HTML of AOC puzzle descriptions to Markdown
+///
+/// Only the subset of HTML used in the puzzle articles is supported: headings, paragraphs, code blocks and inline
+/// code (with highlights), emphasis, (nested) lists, links, horizontal rules and tooltips, which become footnotes.
+/// Other tags are dropped, keeping their content. Code blocks with highlights stay HTML blocks, as Markdown code
+/// blocks can not contain emphasis.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut converter = Converter::default();
+    for token in Tokenizer::new(html) {
+        converter.handle(token);
+    }
+    converter.finish()
+}
+
+/// Mark the code blocks of converted Markdown as plain text, so rustdoc does not compile them as Rust doctests
+pub fn mark_code_blocks_as_text(markdown: &str) -> String {
+    let mut open_fence: Option<&str> = None;
+    let mut result = String::with_capacity(markdown.len());
+    for line in markdown.lines() {
+        let is_fence = line.len() >= 3 && line.chars().all(|c| c == '`');
+        match open_fence {
+            None if is_fence => {
+                open_fence = Some(line);
+                result.push_str(line);
+                result.push_str("text");
+            }
+            Some(fence) if line == fence => {
+                open_fence = None;
+                result.push_str(line);
+            }
+            _ => result.push_str(line),
+        }
+        result.push('\n');
+    }
+    result
+}
+
+/// Decode the HTML entities in a text
+pub(crate) fn unescape_html(text: &str) -> String {
+    ENTITY_REGEX
+        .replace_all(text, |c: &regex::Captures| {
+            let entity = &c[1];
+            let decoded = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(|code| code.ok())
+                    .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| c[0].to_string(), String::from)
+        })
+        .to_string()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Text(String),
+    Open(String, Vec<(String, String)>),
+    Close(String),
+}
+
+/// Splits HTML into tags and text, comments and doctypes are skipped
+struct Tokenizer<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(html: &'a str) -> Self {
+        Self { rest: html }
+    }
+}
+
+impl Iterator for Tokenizer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            if let Some(comment) = self.rest.strip_prefix("<!--") {
+                self.rest = comment.split_once("-->").map_or("", |(_, rest)| rest);
+                continue;
+            }
+            if !self.rest.starts_with('<') {
+                let end = self.rest.find('<').unwrap_or(self.rest.len());
+                let (text, rest) = self.rest.split_at(end);
+                self.rest = rest;
+                return Some(Token::Text(unescape_html(text)));
+            }
+            let end = tag_end(self.rest);
+            let tag = &self.rest[1..end];
+            self.rest = self.rest.get(end + 1..).unwrap_or("");
+            if tag.starts_with('!') || tag.starts_with('?') {
+                continue;
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                return Some(Token::Close(name.trim().to_ascii_lowercase()));
+            }
+            let tag = tag.trim_end_matches('/');
+            let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            let (name, attributes) = tag.split_at(name_end);
+            return Some(Token::Open(
+                name.to_ascii_lowercase(),
+                parse_attributes(attributes),
+            ));
+        }
+    }
+}
+
+/// Position of the `>` closing the tag at the start of the html, ignoring `>` in quoted attribute values
+fn tag_end(html: &str) -> usize {
+    let mut quote = None;
+    for (i, c) in html.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return i,
+            _ => {}
+        }
+    }
+    html.len()
+}
+
+fn parse_attributes(attributes: &str) -> Vec<(String, String)> {
+    let mut result = vec![];
+    let mut rest = attributes.trim_start();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+        let value = if let Some(value) = rest.strip_prefix('=') {
+            let value = value.trim_start();
+            let (value, remaining) = match value.chars().next() {
+                Some(q @ ('"' | '\'')) => value[1..].split_once(q).unwrap_or((&value[1..], "")),
+                _ => value.split_at(value.find(char::is_whitespace).unwrap_or(value.len())),
+            };
+            rest = remaining.trim_start();
+            unescape_html(value)
+        } else {
+            String::new()
+        };
+        result.push((name, value));
+    }
+    result
+}
+
+fn attribute(attributes: &[(String, String)], name: &str) -> Option<String> {
+    attributes
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.clone())
+}
+
+#[derive(Default)]
+struct Converter {
+    blocks: Vec<String>,
+    /// Text of the paragraph, heading or list item being written
+    inline: String,
+    heading: Option<usize>,
+    /// Lines of the list being written, and the current nesting depth
+    list_lines: Vec<String>,
+    list_depth: usize,
+    /// Whether the text being written continues a list item after a nested list
+    continuation: bool,
+    /// Segments of the code block being written, with whether they are highlighted
+    pre: Option<Vec<(bool, String)>>,
+    /// Segments of the inline code span being written, with whether they are highlighted
+    code: Option<Vec<(bool, String)>>,
+    /// Nesting depth of emphasis inside the code block or inline code span
+    code_highlight: usize,
+    links: Vec<Option<String>>,
+    tooltips: Vec<Option<String>>,
+    footnotes: Vec<String>,
+}
+
+impl Converter {
+    fn handle(&mut self, token: Token) {
+        match token {
+            Token::Text(text) => self.text(&text),
+            Token::Open(name, attributes) => self.open(&name, &attributes),
+            Token::Close(name) => self.close(&name),
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let highlighted = self.code_highlight > 0;
+        if let Some(segments) = self.pre.as_mut().or(self.code.as_mut()) {
+            match segments.last_mut() {
+                Some((h, segment)) if *h == highlighted => segment.push_str(text),
+                _ => segments.push((highlighted, text.to_string())),
+            }
+        } else {
+            if self.list_depth > 0 && self.inline.is_empty() && !text.trim().is_empty() {
+                self.inline = "  ".repeat(self.list_depth);
+                self.continuation = true;
+            }
+            push_text(&mut self.inline, text);
+        }
+    }
+
+    fn open(&mut self, name: &str, attributes: &[(String, String)]) {
+        if self.pre.is_some() {
+            if matches!(name, "em" | "strong") {
+                self.code_highlight += 1;
+            }
+            return;
+        }
+        match name {
+            "p" | "div" | "article" => self.flush(),
+            "h1" | "h2" | "h3" | "h4" => {
+                self.flush();
+                self.heading = name[1..].parse().ok();
+            }
+            "pre" => {
+                self.flush();
+                self.pre = Some(vec![]);
+            }
+            "code" => self.code = Some(vec![]),
+            "em" | "strong" if self.code.is_some() => self.code_highlight += 1,
+            "em" => self.inline.push('*'),
+            "strong" => self.inline.push_str("**"),
+            "ul" | "ol" => {
+                self.flush();
+                self.list_depth += 1;
+            }
+            "li" => {
+                self.flush();
+                self.inline = format!("{}- ", "  ".repeat(self.list_depth.saturating_sub(1)));
+            }
+            "a" => {
+                self.links.push(attribute(attributes, "href"));
+                self.inline.push('[');
+            }
+            "span" => self.tooltips.push(attribute(attributes, "title")),
+            "hr" => {
+                self.flush();
+                self.blocks.push("***".to_string());
+            }
+            "br" => self.inline.push_str("  \n"),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        match name {
+            "pre" => {
+                self.code_highlight = 0;
+                if let Some(pre) = self.pre.take() {
+                    self.blocks.push(render_code_block(&pre));
+                }
+            }
+            "em" | "strong" if self.pre.is_some() || self.code.is_some() => {
+                self.code_highlight = self.code_highlight.saturating_sub(1)
+            }
+            _ if self.pre.is_some() => {}
+            "p" | "div" | "article" | "li" => self.flush(),
+            "h1" | "h2" | "h3" | "h4" => self.flush(),
+            "code" => {
+                self.code_highlight = 0;
+                if let Some(code) = self.code.take() {
+                    self.inline.push_str(&render_code(&code));
+                }
+            }
+            "em" => self.close_emphasis("*"),
+            "strong" => self.close_emphasis("**"),
+            "ul" | "ol" => {
+                self.flush();
+                self.list_depth = self.list_depth.saturating_sub(1);
+                if self.list_depth == 0 && !self.list_lines.is_empty() {
+                    self.blocks.push(self.list_lines.join("\n"));
+                    self.list_lines.clear();
+                }
+            }
+            "a" => {
+                let href = self.links.pop().flatten().unwrap_or_default();
+                let href = if href.starts_with('/') {
+                    format!("{}{}", AOC_ORIGIN, href)
+                } else {
+                    href
+                };
+                self.inline.push_str(&format!("]({})", href));
+            }
+            "span" => {
+                if let Some(title) = self.tooltips.pop().flatten() {
+                    self.footnotes.push(title);
+                    self.inline
+                        .push_str(&format!("[^{}]", self.footnotes.len()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Close emphasis, moving trailing whitespace out of it, as `*word *` is not emphasis in Markdown
+    fn close_emphasis(&mut self, marker: &str) {
+        let trailing_space = self.inline.ends_with(' ');
+        if trailing_space {
+            self.inline.pop();
+        }
+        self.inline.push_str(marker);
+        if trailing_space {
+            self.inline.push(' ');
+        }
+    }
+
+    /// End the paragraph, heading or list item being written
+    fn flush(&mut self) {
+        let text = std::mem::take(&mut self.inline);
+        if let Some(level) = self.heading.take() {
+            if !text.trim().is_empty() {
+                self.blocks
+                    .push(format!("{} {}", "#".repeat(level), text.trim()));
+            }
+        } else if self.list_depth > 0 {
+            // An item is only written when it has content besides its `- ` marker, continuations are separate
+            // paragraphs of the item
+            if text.trim_start().len() > 1 {
+                if std::mem::take(&mut self.continuation) {
+                    self.list_lines.push(String::new());
+                }
+                self.list_lines.push(text.trim_end().to_string());
+            }
+        } else if !text.trim().is_empty() {
+            let text = text.trim();
+            self.blocks.push(text.to_string());
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        if !self.list_lines.is_empty() {
+            self.blocks.push(self.list_lines.join("\n"));
+        }
+        for (n, footnote) in (1..).zip(&self.footnotes) {
+            let mut text = String::new();
+            push_text(&mut text, footnote);
+            self.blocks.push(format!("[^{}]: {}", n, text.trim()));
+        }
+        let mut markdown = self.blocks.join("\n\n");
+        markdown.push('\n');
+        markdown
+    }
+}
+
+/// Append text to Markdown, collapsing whitespace like HTML does and escaping Markdown syntax
+fn push_text(markdown: &mut String, text: &str) {
+    for c in text.chars() {
+        if c.is_whitespace() && c != '\u{a0}' {
+            if !markdown.is_empty() && !markdown.ends_with(' ') {
+                markdown.push(' ');
+            }
+        } else {
+            if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>') {
+                markdown.push('\\');
+            }
+            markdown.push(c);
+        }
+    }
+}
+
+/// Render a code block, as an HTML block with `<em>` highlights when it has any
+fn render_code_block(segments: &[(bool, String)]) -> String {
+    let mut code = segments
+        .iter()
+        .map(|(_, text)| text.as_str())
+        .collect::<String>();
+    if !code.ends_with('\n') {
+        code.push('\n');
+    }
+    if !segments
+        .iter()
+        .any(|(highlighted, text)| *highlighted && !text.is_empty())
+    {
+        let fence = "`".repeat(longest_backtick_run(&code).max(2) + 1);
+        return format!("{}\n{}{}", fence, code, fence);
+    }
+    let html = segments
+        .iter()
+        .filter(|(_, text)| !text.is_empty())
+        .map(|(highlighted, text)| match highlighted {
+            true => format!("<em>{}</em>", escape_html(text)),
+            false => escape_html(text),
+        })
+        .collect::<String>();
+    let newline = if html.ends_with('\n') { "" } else { "\n" };
+    format!("<pre><code>{}{}</code></pre>", html, newline)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Render an inline code span, highlighted segments become bold code spans
+fn render_code(segments: &[(bool, String)]) -> String {
+    segments
+        .iter()
+        .filter(|(_, text)| !text.is_empty())
+        .map(|(highlighted, text)| {
+            let fence = "`".repeat(longest_backtick_run(text) + 1);
+            let padding = if text.starts_with('`') || text.ends_with('`') {
+                " "
+            } else {
+                ""
+            };
+            let span = format!("{0}{1}{2}{1}{0}", fence, padding, text);
+            if *highlighted {
+                format!("**{}**", span)
+            } else {
+                span
+            }
+        })
+        .collect()
+}
+
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::*;
+    use crate::process_puzzle_html;
+
+    #[test]
+    fn test_unescape_html() {
+        assert_eq!(
+            unescape_html("&lt;a&gt; &amp;amp; &quot;&#39;&#x41;&unknown;"),
+            "<a> &amp; \"'A&unknown;"
+        );
+    }
+
+    #[test]
+    fn test_inline() {
+        assert_eq!(
+            html_to_markdown(
+                r#"<p>Find <em>all</em> the <code>a*b</code> and <code>x<em>y</em>z</code>, see <a href="/2025/day/1/input">input</a>.</p>"#
+            ),
+            "Find *all* the `a*b` and `x`**`y`**`z`, see [input](https://adventofcode.com/2025/day/1/input).\n"
+        );
+        assert_eq!(
+            html_to_markdown(r#"<p>Escape 2*3 [x] and <code>`</code></p>"#),
+            "Escape 2\\*3 \\[x\\] and `` ` ``\n"
+        );
+    }
+
+    #[test]
+    fn test_nested_lists() {
+        assert_eq!(
+            html_to_markdown("<ul>\n<li>One\n<ul><li>Two</li><li><em>Three</em></li></ul></li>\n<li>Four</li>\n</ul><p>After</p>"),
+            "- One\n  - Two\n  - *Three*\n- Four\n\nAfter\n"
+        );
+    }
+
+    #[test]
+    fn test_mark_code_blocks_as_text() {
+        let markdown = html_to_markdown(
+            "<pre><code>a\n```\nb\n</code></pre><p>After</p><pre><code>c</code></pre>",
+        );
+        assert_eq!(
+            mark_code_blocks_as_text(&markdown),
+            "````text\na\n```\nb\n````\n\nAfter\n\n```text\nc\n```\n"
+        );
+    }
+
+    /// Every `*.html` page in `test_data/markdown` is converted and compared with the `*.md` file next to it.
+    ///
+    /// Set `UPDATE_GOLDEN=1` to write the current output to the `*.md` files.
+    #[test]
+    fn test_golden_files() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data/markdown");
+        let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+        let mut pages = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|e| e == "html"))
+            .collect::<Vec<_>>();
+        pages.sort();
+        assert!(!pages.is_empty(), "no golden files in {}", dir.display());
+        for page in pages {
+            let markdown = process_puzzle_html(fs::read_to_string(&page).unwrap()).unwrap();
+            let golden = page.with_extension("md");
+            if update {
+                fs::write(&golden, &markdown).unwrap();
+                continue;
+            }
+            let expected = fs::read_to_string(&golden)
+                .unwrap_or_else(|_| panic!("missing {}", golden.display()));
+            assert_eq!(markdown, expected, "{} differs", golden.display());
+        }
+    }
+}
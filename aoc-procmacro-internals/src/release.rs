@@ -0,0 +1,150 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{clock::get_clock, AocError};
+
+const FIRST_YEAR: u32 = 2015;
+/// Puzzles unlock at midnight US Eastern, AOC runs in December so that is always UTC-5
+const RELEASE_HOUR_UTC: u64 = 5;
+/// Extra time to wait after a release, so a slightly fast clock does not request too early
+const RELEASE_MARGIN: Duration = Duration::from_secs(2);
+/// Longest single sleep while waiting, so the wait recovers from the system being suspended
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// Number of puzzle days in an event
+pub fn puzzle_days(year: u32) -> u32 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// Moment the puzzle of a day unlocks
+pub fn release_time(day: u32, year: u32) -> Result<SystemTime, AocError> {
+    if year < FIRST_YEAR || day == 0 || day > puzzle_days(year) {
+        return Err(AocError::NotFound(format!(
+            "there is no puzzle for day {} of {}",
+            day, year
+        )));
+    }
+    let days = days_since_epoch(year as i64, 12, day as i64) as u64;
+    Ok(UNIX_EPOCH + Duration::from_secs(days * 86400 + RELEASE_HOUR_UTC * 3600))
+}
+
+/// Time left until the puzzle of a day unlocks, `None` once it is released
+pub fn time_until_release(day: u32, year: u32) -> Result<Option<Duration>, AocError> {
+    let release = release_time(day, year)?;
+    Ok(release.duration_since(get_clock().now()).ok())
+}
+
+/// Refuse requests for puzzle data which is not released yet
+pub(crate) fn ensure_released(day: u32, year: u32) -> Result<(), AocError> {
+    match time_until_release(day, year)? {
+        Some(remaining) => Err(AocError::NotReleased {
+            day,
+            year,
+            remaining,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Block until the puzzle of a day is released
+pub fn wait_for_release(day: u32, year: u32) -> Result<(), AocError> {
+    let clock = get_clock();
+    while let Some(remaining) = time_until_release(day, year)? {
+        clock.sleep((remaining + RELEASE_MARGIN).min(MAX_SLEEP));
+    }
+    Ok(())
+}
+
+/// Format a duration as a countdown, e.g. `2d 3h 0m 5s`
+pub fn format_countdown(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) = (
+        secs / 86400,
+        secs % 86400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", seconds),
+        (0, 0, _) => format!("{}m {}s", minutes, seconds),
+        (0, _, _) => format!("{}h {}m {}s", hours, minutes, seconds),
+        _ => format!("{}d {}h {}m {}s", days, hours, minutes, seconds),
+    }
+}
+
+/// Days between 1970-01-01 and a date in the proleptic Gregorian calendar
+fn days_since_epoch(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        clock::{set_clock, FixedClock},
+        get_aoc_data,
+        test_utils::TestEnv,
+        AocDataType, FixtureTransport, HttpMethod, HttpResponse,
+    };
+
+    #[test]
+    fn test_release_time() {
+        assert_eq!(
+            release_time(1, 2025).unwrap(),
+            UNIX_EPOCH + Duration::from_secs(1764565200)
+        );
+        assert_eq!(
+            release_time(7, 2025).unwrap(),
+            UNIX_EPOCH + Duration::from_secs(1765083600)
+        );
+        assert!(release_time(25, 2024).is_ok());
+        assert!(release_time(13, 2025).is_err());
+        assert!(release_time(0, 2025).is_err());
+        assert!(release_time(1, 2014).is_err());
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(5)), "5s");
+        assert_eq!(format_countdown(Duration::from_secs(65)), "1m 5s");
+        assert_eq!(format_countdown(Duration::from_secs(3600)), "1h 0m 0s");
+        assert_eq!(format_countdown(Duration::from_secs(183605)), "2d 3h 0m 5s");
+    }
+
+    #[test]
+    fn test_not_released() {
+        let env = TestEnv::new(
+            "not-released",
+            FixtureTransport::new().with_response(
+                HttpMethod::Get,
+                "2025/day/7/input",
+                HttpResponse::new(200, "1\n2\n"),
+            ),
+        );
+        let release = release_time(7, 2025).unwrap();
+        set_clock(Arc::new(FixedClock::new(
+            release - Duration::from_secs(3725),
+        )));
+
+        let result = get_aoc_data(AocDataType::Input, 7, 2025);
+        assert!(matches!(
+            result,
+            Err(AocError::NotReleased { remaining, .. }) if remaining == Duration::from_secs(3725)
+        ));
+        assert!(env.transport.requests().is_empty());
+
+        wait_for_release(7, 2025).unwrap();
+        assert_eq!(time_until_release(7, 2025).unwrap(), None);
+        assert_eq!(get_aoc_data(AocDataType::Input, 7, 2025).unwrap(), "1\n2\n");
+    }
+}
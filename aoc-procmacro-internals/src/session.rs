@@ -0,0 +1,171 @@
+use std::{env, fs, path::PathBuf, sync::Mutex};
+
+use crate::{
+    config::get_config_or_default,
+    fetcher::{get_url, send_to_aoc},
+    AocError, HttpRequest, HttpResponse,
+};
+
+pub const AOC_SESSION_ENV_VAR: &str = "AOC_SESSION";
+
+lazy_static::lazy_static! {
+    static ref AOC_SESSION: Mutex<Option<String>> = Mutex::new(
+        env::var_os(AOC_SESSION_ENV_VAR)
+            .and_then(|v| v.into_string().ok())
+            .or_else(|| get_config_or_default().session())
+            .or_else(read_user_session)
+    );
+    static ref USER_REGEX: regex::Regex = regex::Regex::new(r#"<div class="user">([^<]*)"#).unwrap();
+    static ref COOKIE_EXPIRES_REGEX: regex::Regex = regex::RegexBuilder::new(r#"^session=.*;\s*expires=([^;]+)"#)
+        .case_insensitive(true)
+        .build()
+        .unwrap();
+}
+
+/// Set the AOC session to use when downloading data.
+pub fn set_session(session_id: String) {
+    let mut session = AOC_SESSION.lock().unwrap();
+    *session = Some(session_id);
+}
+
+pub(crate) fn get_session() -> Option<String> {
+    AOC_SESSION.lock().unwrap().clone()
+}
+
+/// Location of the session file in the user configuration directory, e.g. `~/.config/aoc/session`
+pub fn user_session_file() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .map(|dir| dir.join("aoc").join("session"))
+}
+
+fn read_user_session() -> Option<String> {
+    let session = fs::read_to_string(user_session_file()?).ok()?;
+    Some(session.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// The account a session belongs to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionInfo {
+    pub user: String,
+    /// Expiry of the session cookie, only known when the response sets the cookie
+    pub expires: Option<String>,
+}
+
+/// Check whether the session is accepted by AOC, with a single request for a small page
+pub fn check_session() -> Result<SessionInfo, AocError> {
+    let response = send_to_aoc(HttpRequest::get(get_url("settings")))?;
+    if !response.is_success() {
+        return Err(AocError::HttpStatus {
+            status: response.status,
+            body: response.body,
+        });
+    }
+    let user = logged_in_user(&response.body).ok_or(AocError::SessionExpired)?;
+    let expires = response
+        .headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("set-cookie"))
+        .find_map(|(_, value)| COOKIE_EXPIRES_REGEX.captures(value))
+        .map(|c| c[1].trim().to_string());
+    Ok(SessionInfo { user, expires })
+}
+
+/// Whether AOC refused a request because the session is invalid or expired
+///
+/// AOC responds with a 400 asking to log in to requests for personal data, e.g. the puzzle input, and serves the
+/// login page where an account is needed. Other 400 responses are not about the session.
+pub(crate) fn is_session_rejected(response: &HttpResponse) -> bool {
+    (response.status == 400 && response.body.contains("Please log in"))
+        || (response.body.contains("/auth/login") && logged_in_user(&response.body).is_none())
+}
+
+/// Name of the user in the header of an AOC page
+fn logged_in_user(page: &str) -> Option<String> {
+    USER_REGEX
+        .captures(page)
+        .map(|c| c[1].trim().to_string())
+        .filter(|user| !user.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_aoc_data, test_utils::TestEnv, AocDataType, FixtureTransport, HttpMethod};
+
+    const SETTINGS_PAGE: &str = r#"<!DOCTYPE html><html><body><header><div><div class="user">Test User <span class="star-count">12*</span></div></div></header><main>Settings</main></body></html>"#;
+    const LOGIN_PAGE: &str = r#"<!DOCTYPE html><html><body><header><div class="user"></div><a href="/2025/auth/login">[Log In]</a></header><main>To play, please identify yourself</main></body></html>"#;
+
+    #[test]
+    fn test_check_session() {
+        let mut response = HttpResponse::new(200, SETTINGS_PAGE);
+        response.headers.push((
+            "Set-Cookie".to_string(),
+            "session=abc; Expires=Sat, 05 Dec 2026 05:00:00 GMT; Path=/".to_string(),
+        ));
+        let _env = TestEnv::new(
+            "session",
+            FixtureTransport::new().with_response(HttpMethod::Get, "settings", response),
+        );
+        assert_eq!(
+            check_session().unwrap(),
+            SessionInfo {
+                user: "Test User".to_string(),
+                expires: Some("Sat, 05 Dec 2026 05:00:00 GMT".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn test_session_expired() {
+        let env = TestEnv::new(
+            "session-expired",
+            FixtureTransport::new()
+                .with_response(
+                    HttpMethod::Get,
+                    "settings",
+                    HttpResponse::new(200, LOGIN_PAGE),
+                )
+                .with_response(
+                    HttpMethod::Get,
+                    "2025/day/1/input",
+                    HttpResponse::new(
+                        400,
+                        "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+                    ),
+                ),
+        );
+        assert!(matches!(check_session(), Err(AocError::SessionExpired)));
+        assert!(matches!(
+            get_aoc_data(AocDataType::Input, 1, 2025),
+            Err(AocError::SessionExpired)
+        ));
+        assert!(!env.data_dir.join("2025/1/input").exists());
+    }
+
+    #[test]
+    fn test_session_accepted() {
+        let _env = TestEnv::new(
+            "session-accepted",
+            FixtureTransport::new()
+                .with_response(
+                    HttpMethod::Get,
+                    "settings",
+                    HttpResponse::new(200, SETTINGS_PAGE),
+                )
+                .with_response(
+                    HttpMethod::Get,
+                    "2025/day/1/input",
+                    HttpResponse::new(400, "Bad Request"),
+                ),
+        );
+        // Without a cookie header the expiry is unknown
+        assert_eq!(check_session().unwrap().expires, None);
+        assert!(matches!(
+            get_aoc_data(AocDataType::Input, 1, 2025),
+            Err(AocError::HttpStatus { status: 400, .. })
+        ));
+    }
+}
@@ -0,0 +1,240 @@
+use std::time::Duration;
+
+use crate::{
+    fetcher::{get_url, send_to_aoc},
+    transport::HttpRequest,
+    AnswerLedger, AnswerStatus, AocError,
+};
+
+/// Outcome of an answer submitted to AOC
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, wait before trying again
+    RateLimited(Duration),
+    /// The part was already solved, or part 1 has not been solved yet
+    WrongLevel,
+    /// The answer is the correct answer in the answer ledger, it was not submitted again
+    AlreadyVerified,
+}
+
+impl std::fmt::Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "correct"),
+            SubmitOutcome::Wrong => write!(f, "wrong"),
+            SubmitOutcome::TooHigh => write!(f, "wrong, too high"),
+            SubmitOutcome::TooLow => write!(f, "wrong, too low"),
+            SubmitOutcome::RateLimited(wait) => {
+                write!(f, "rate limited, wait {}s", wait.as_secs())
+            }
+            SubmitOutcome::WrongLevel => write!(f, "already solved or locked"),
+            SubmitOutcome::AlreadyVerified => write!(f, "already verified, not submitted"),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref RESPONSE_REGEX: regex::Regex = regex::RegexBuilder::new(r#"<article>(.+?)<\/article>"#)
+        .dot_matches_new_line(true)
+        .build()
+        .unwrap();
+    static ref WAIT_REGEX: regex::Regex = regex::Regex::new(r#"You have (?:(\d+)m )?(\d+)s left to wait"#).unwrap();
+}
+
+/// Submit an answer for a puzzle part to AOC.
+///
+/// Answers which are known to be wrong based on the answer ledger are refused without submitting, and
+/// verified answers are not submitted again. The verdict of submitted answers is recorded in the ledger.
+pub fn submit_answer(
+    day: u32,
+    year: u32,
+    part: u8,
+    answer: &str,
+) -> Result<SubmitOutcome, AocError> {
+    let mut ledger = AnswerLedger::load(day, year)?;
+    match ledger.check(part, answer) {
+        AnswerStatus::Unknown => {}
+        AnswerStatus::Verified => return Ok(SubmitOutcome::AlreadyVerified),
+        AnswerStatus::KnownWrong(reason) => {
+            return Err(AocError::Refused(format!("{} is {}", answer, reason)))
+        }
+    }
+    let outcome = post_answer(day, year, part, answer)?;
+    ledger.record(part, answer, &outcome)?;
+    Ok(outcome)
+}
+
+fn post_answer(day: u32, year: u32, part: u8, answer: &str) -> Result<SubmitOutcome, AocError> {
+    let path = format!("{}/day/{}/answer", year, day);
+    let response = send_to_aoc(
+        HttpRequest::post(get_url(&path))
+            .with_form_field("level", part.to_string())
+            .with_form_field("answer", answer.to_string()),
+    )?;
+    if !response.is_success() {
+        return Err(AocError::HttpStatus {
+            status: response.status,
+            body: response.body,
+        });
+    }
+    parse_submit_response(&response.body)
+}
+
+/// Parse the response page of the answer endpoint into an outcome
+pub fn parse_submit_response(text: &str) -> Result<SubmitOutcome, AocError> {
+    let message = RESPONSE_REGEX
+        .captures(text)
+        .map(|c| c.get(1).unwrap().as_str())
+        .unwrap_or(text);
+    if message.contains("That's the right answer") {
+        Ok(SubmitOutcome::Correct)
+    } else if message.contains("That's not the right answer") {
+        if message.contains("your answer is too high") {
+            Ok(SubmitOutcome::TooHigh)
+        } else if message.contains("your answer is too low") {
+            Ok(SubmitOutcome::TooLow)
+        } else {
+            Ok(SubmitOutcome::Wrong)
+        }
+    } else if message.contains("You gave an answer too recently") {
+        let wait = WAIT_REGEX
+            .captures(message)
+            .map(|c| {
+                let minutes: u64 = c.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+                let seconds: u64 = c.get(2).unwrap().as_str().parse().unwrap();
+                minutes * 60 + seconds
+            })
+            .unwrap_or(60);
+        Ok(SubmitOutcome::RateLimited(Duration::from_secs(wait)))
+    } else if message.contains("You don't seem to be solving the right level") {
+        Ok(SubmitOutcome::WrongLevel)
+    } else {
+        Err(AocError::Parse(format!(
+            "unrecognized answer response: {}",
+            message.trim()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::TestEnv, FixtureTransport, HttpMethod, HttpResponse};
+
+    fn wrap(message: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    #[test]
+    fn test_correct() {
+        let text = wrap("That's the right answer!  You are one gold star closer to decorating the North Pole. <a href=\"/2025/day/1#part2\">[Continue to Part Two]</a>");
+        assert_eq!(
+            parse_submit_response(&text).unwrap(),
+            SubmitOutcome::Correct
+        );
+    }
+
+    #[test]
+    fn test_wrong() {
+        let text = wrap("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.");
+        assert_eq!(
+            parse_submit_response(&text).unwrap(),
+            SubmitOutcome::TooHigh
+        );
+        let text = wrap("That's not the right answer; your answer is too low.  Please wait one minute before trying again.");
+        assert_eq!(parse_submit_response(&text).unwrap(), SubmitOutcome::TooLow);
+        let text = wrap("That's not the right answer.  If you're stuck, make sure you're using the full input data.");
+        assert_eq!(parse_submit_response(&text).unwrap(), SubmitOutcome::Wrong);
+    }
+
+    #[test]
+    fn test_rate_limited() {
+        let text = wrap("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2025/day/1\">[Return to Day 1]</a>");
+        assert_eq!(
+            parse_submit_response(&text).unwrap(),
+            SubmitOutcome::RateLimited(Duration::from_secs(65))
+        );
+        let text = wrap("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 37s left to wait.");
+        assert_eq!(
+            parse_submit_response(&text).unwrap(),
+            SubmitOutcome::RateLimited(Duration::from_secs(37))
+        );
+    }
+
+    #[test]
+    fn test_wrong_level() {
+        let text = wrap("You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2025/day/1\">[Return to Day 1]</a>");
+        assert_eq!(
+            parse_submit_response(&text).unwrap(),
+            SubmitOutcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn test_unknown() {
+        assert!(parse_submit_response(&wrap("Something else")).is_err());
+    }
+
+    #[test]
+    fn test_submit_answer() {
+        let env = TestEnv::new(
+            "submit",
+            FixtureTransport::new().with_response(
+                HttpMethod::Post,
+                "2025/day/1/answer",
+                HttpResponse::new(
+                    200,
+                    &wrap("That's not the right answer; your answer is too high."),
+                ),
+            ),
+        );
+        assert_eq!(
+            submit_answer(1, 2025, 1, "100").unwrap(),
+            SubmitOutcome::TooHigh
+        );
+        let requests = env.transport.requests();
+        assert_eq!(
+            requests[0].form,
+            vec![
+                ("level".to_string(), "1".to_string()),
+                ("answer".to_string(), "100".to_string())
+            ]
+        );
+
+        // Known wrong answers are refused without contacting AOC
+        assert!(matches!(
+            submit_answer(1, 2025, 1, "150"),
+            Err(AocError::Refused(_))
+        ));
+        assert_eq!(env.transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_submit_verified_answer() {
+        let env = TestEnv::new(
+            "submit_verified",
+            FixtureTransport::new().with_response(
+                HttpMethod::Post,
+                "2025/day/1/answer",
+                HttpResponse::new(200, &wrap("That's the right answer!")),
+            ),
+        );
+        assert_eq!(
+            submit_answer(1, 2025, 1, "42").unwrap(),
+            SubmitOutcome::Correct
+        );
+        // Verified answers are not submitted again
+        assert_eq!(
+            submit_answer(1, 2025, 1, "42").unwrap(),
+            SubmitOutcome::AlreadyVerified
+        );
+        assert_eq!(env.transport.requests().len(), 1);
+    }
+}
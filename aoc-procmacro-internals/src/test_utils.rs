@@ -0,0 +1,50 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use crate::{
+    set_cache_passphrase, set_clock, set_data_dir, set_offline, set_session, set_throttle_interval,
+    set_transport, FixtureTransport, SystemClock,
+};
+
+lazy_static::lazy_static! {
+    static ref TEST_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// Isolated fetcher environment, with its own data directory and fixture transport
+pub(crate) struct TestEnv {
+    pub transport: Arc<FixtureTransport>,
+    pub data_dir: PathBuf,
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl TestEnv {
+    pub fn new(name: &str, transport: FixtureTransport) -> Self {
+        // The fetcher settings are global, tests using them can not run in parallel
+        let guard = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let data_dir =
+            std::env::temp_dir().join(format!("aoc-data-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        let transport = Arc::new(transport);
+        set_data_dir(data_dir.clone());
+        set_session("test-session".to_string());
+        set_offline(false);
+        set_throttle_interval(Duration::ZERO);
+        set_transport(transport.clone());
+        set_clock(Arc::new(SystemClock));
+        set_cache_passphrase(None);
+        Self {
+            transport,
+            data_dir,
+            _guard: guard,
+        }
+    }
+}
+
+impl Drop for TestEnv {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.data_dir);
+    }
+}
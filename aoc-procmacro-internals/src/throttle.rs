@@ -0,0 +1,99 @@
+use std::{
+    env, fs,
+    io::{Read, Seek, SeekFrom, Write},
+    sync::Mutex,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{config::get_config_or_default, fetcher::get_data_root, AocError};
+
+pub const AOC_THROTTLE_ENV_VAR: &str = "AOC_THROTTLE";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+const LAST_REQUEST_FILE_NAME: &str = ".last_request";
+
+lazy_static::lazy_static! {
+    static ref THROTTLE_INTERVAL: Mutex<Duration> = Mutex::new(
+        env::var_os(AOC_THROTTLE_ENV_VAR)
+            .and_then(|v| v.into_string().ok())
+            .and_then(|v| v.parse::<f64>().ok())
+            .or(get_config_or_default().throttle)
+            .map(Duration::from_secs_f64)
+            .unwrap_or(DEFAULT_INTERVAL)
+    );
+}
+
+/// Set the minimum time between two requests to AOC.
+pub fn set_throttle_interval(interval: Duration) {
+    let mut throttle_interval = THROTTLE_INTERVAL.lock().unwrap();
+    *throttle_interval = interval;
+}
+
+/// Block until the minimum interval since the last request to AOC has passed.
+///
+/// The time of the last request is stored in the `aoc_data` directory, the file is locked while waiting so
+/// parallel processes (e.g. multiple rustc instances expanding `aoc_puzzle`) take turns.
+pub(crate) fn throttle() -> Result<(), AocError> {
+    let interval = *THROTTLE_INTERVAL.lock().unwrap();
+    let data_root = get_data_root()?;
+    fs::create_dir_all(&data_root)?;
+    let mut file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(data_root.join(LAST_REQUEST_FILE_NAME))?;
+    file.lock()?;
+
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+    let last_request = content
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+    thread::sleep(remaining_wait(last_request, SystemTime::now(), interval));
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    write!(file, "{}", now.as_millis())?;
+    Ok(())
+}
+
+fn remaining_wait(
+    last_request: Option<SystemTime>,
+    now: SystemTime,
+    interval: Duration,
+) -> Duration {
+    last_request
+        .and_then(|last| (last + interval).duration_since(now).ok())
+        .map_or(Duration::ZERO, |wait| wait.min(interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_wait() {
+        let interval = Duration::from_secs(5);
+        let now = SystemTime::now();
+        assert_eq!(remaining_wait(None, now, interval), Duration::ZERO);
+        assert_eq!(
+            remaining_wait(Some(now - Duration::from_secs(2)), now, interval),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            remaining_wait(Some(now - Duration::from_secs(10)), now, interval),
+            Duration::ZERO
+        );
+        // Clock skew should never cause waiting longer than the interval
+        assert_eq!(
+            remaining_wait(Some(now + Duration::from_secs(60)), now, interval),
+            interval
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use reqwest::blocking::Client;
+
+use crate::AocError;
+
+lazy_static::lazy_static! {
+    static ref TRANSPORT: Mutex<Arc<dyn Transport>> = Mutex::new(Arc::new(ReqwestTransport::default()));
+}
+
+/// Set the transport used for all requests to AOC.
+pub fn set_transport(transport: Arc<dyn Transport>) {
+    let mut current = TRANSPORT.lock().unwrap();
+    *current = transport;
+}
+
+pub(crate) fn get_transport() -> Arc<dyn Transport> {
+    TRANSPORT.lock().unwrap().clone()
+}
+
+/// Sends http requests to AOC, or something pretending to be AOC
+pub trait Transport: Send + Sync {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, AocError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HttpMethod {
+    Get,
+    Post,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: HttpMethod,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub form: Vec<(String, String)>,
+}
+
+impl HttpRequest {
+    pub fn get(url: String) -> Self {
+        Self::new(HttpMethod::Get, url)
+    }
+
+    pub fn post(url: String) -> Self {
+        Self::new(HttpMethod::Post, url)
+    }
+
+    fn new(method: HttpMethod, url: String) -> Self {
+        Self {
+            method,
+            url,
+            headers: vec![],
+            form: vec![],
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: String) -> Self {
+        self.headers.push((name.to_string(), value));
+        self
+    }
+
+    pub fn with_form_field(mut self, name: &str, value: String) -> Self {
+        self.form.push((name.to_string(), value));
+        self
+    }
+
+    /// Path of the url, without the host and leading slash
+    pub fn path(&self) -> &str {
+        let without_scheme = self
+            .url
+            .split_once("://")
+            .map_or(self.url.as_str(), |(_, rest)| rest);
+        without_scheme.split_once('/').map_or("", |(_, path)| path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: vec![],
+            body: body.to_string(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn is_server_error(&self) -> bool {
+        (500..600).contains(&self.status)
+    }
+
+    /// Get a header value, header names are case insensitive
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Transport sending requests over the network
+#[derive(Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, AocError> {
+        let mut builder = match request.method {
+            HttpMethod::Get => self.client.get(&request.url),
+            HttpMethod::Post => self.client.post(&request.url).form(&request.form),
+        };
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder
+            .send()
+            .map_err(|e| AocError::Transport(e.to_string()))?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|v| (name.to_string(), v.to_string()))
+            })
+            .collect();
+        let body = response
+            .text()
+            .map_err(|e| AocError::Transport(e.to_string()))?;
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// Transport answering requests with recorded responses, requests without a fixture get a 404
+#[derive(Default)]
+pub struct FixtureTransport {
+    fixtures: HashMap<(HttpMethod, String), HttpResponse>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl FixtureTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a response for a request path, e.g. `2025/day/1/input`
+    pub fn with_response(mut self, method: HttpMethod, path: &str, response: HttpResponse) -> Self {
+        self.fixtures.insert((method, path.to_string()), response);
+        self
+    }
+
+    /// All requests received so far
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Transport for FixtureTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, AocError> {
+        self.requests.lock().unwrap().push(request.clone());
+        Ok(self
+            .fixtures
+            .get(&(request.method, request.path().to_string()))
+            .cloned()
+            .unwrap_or_else(|| HttpResponse::new(404, "404 Not Found")))
+    }
+}
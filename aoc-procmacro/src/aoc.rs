@@ -1,5 +1,6 @@
 use std::sync::Mutex;
 
+use aoc_procmacro_internals::get_config;
 use darling::{ast::NestedMeta, FromMeta};
 use proc_macro2::TokenStream;
 
@@ -12,7 +13,10 @@ lazy_static::lazy_static! {
 }
 
 pub fn get_year() -> Option<u32> {
-    *AOC_YEAR.lock().unwrap()
+    AOC_YEAR
+        .lock()
+        .unwrap()
+        .or(get_config().ok().and_then(|config| config.year))
 }
 
 pub(crate) fn aoc_impl(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -24,9 +28,16 @@ pub(crate) fn aoc_impl(args: TokenStream, input: TokenStream) -> TokenStream {
         Ok(v) => v,
         Err(e) => return e.write_errors(),
     };
-
-    let mut val = AOC_YEAR.lock().unwrap();
-    *val = Some(args.year);
+    if let Err(e) = get_config() {
+        let mut output = darling::Error::custom(e).write_errors();
+        output.extend(input);
+        return output;
+    }
+
+    if let Some(year) = args.year {
+        let mut val = AOC_YEAR.lock().unwrap();
+        *val = Some(year);
+    }
 
     input
 }
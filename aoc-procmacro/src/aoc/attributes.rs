@@ -2,5 +2,6 @@ use darling::FromMeta;
 
 #[derive(Debug, FromMeta)]
 pub struct AocAttributes {
-    pub year: u32,
+    /// Falls back to the year in `aoc.toml`
+    pub year: Option<u32>,
 }
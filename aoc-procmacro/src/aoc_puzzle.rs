@@ -1,14 +1,22 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
 use darling::{ast::NestedMeta, FromMeta};
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use aoc_procmacro_internals::{get_aoc_data, AocDataType};
+use aoc_procmacro_internals::{
+    cached_puzzle_text, cached_text_trigger, get_config, mark_code_blocks_as_text,
+    text_refresh_trigger, update_puzzle_text, AocConfig,
+};
 
 use super::aoc::get_year;
 
 mod attributes;
 
-use attributes::{validate_day, AocAttributes};
+use attributes::{validate_day, AocAttributes, ExampleAttributes};
 
 const MISSING_DAY_ERROR: &str =
     "Could not determine puzzle day. Use one of these methods to define the day:
@@ -29,30 +37,41 @@ pub(crate) fn aoc_puzzle_impl(args: TokenStream, input: TokenStream) -> TokenStr
         Ok(v) => v,
         Err(e) => return e.write_errors(),
     };
+    let config = match get_config() {
+        Ok(config) => config,
+        Err(e) => return darling::Error::custom(e).write_errors(),
+    };
 
     let (aoc_day, aoc_year) = match extract_day_year(&input, args.day) {
         Ok(dy) => dy,
         Err(e) => return e.write_errors(),
     };
 
-    let puzzle_description = match get_aoc_data(AocDataType::Text, aoc_day, aoc_year) {
-        Ok(description) => description,
-        Err(e) => format!(
-            "Failed to get puzzle description for day {} ({}): {}",
-            aoc_day, aoc_year, e
-        ),
-    };
-
     let ident = &input.ident;
-    let doc_text = puzzle_description.replace("```", "```text");
+    let (doc, refresh_trigger) = if args.doc.unwrap_or(true) {
+        let (description, trigger) = puzzle_description(config, aoc_day, aoc_year);
+        let doc_text = mark_code_blocks_as_text(&description);
+        (
+            Some(quote! { #[doc = #doc_text] }),
+            trigger.map(|path| include_file(&path)),
+        )
+    } else {
+        (None, None)
+    };
+    let solution = if args.combined {
+        quote! { aoc_core::Combined(<#ident as ::core::default::Default>::default()) }
+    } else {
+        quote! { <#ident as ::core::default::Default>::default() }
+    };
+    let example_tests = example_tests(ident, args.combined, &args.examples, aoc_day, aoc_year);
 
     let expanded = quote! {
-        #[doc = #doc_text]
+        #doc
         #input
 
         pub fn register_solution(solutions: &mut aoc_core::SolutionCollection) {
             let wrapper = aoc_core::SolutionWrapper::new(
-                #ident::default(),
+                #solution,
                 aoc_core::SolutionProps {
                     day: #aoc_day,
                     year: #aoc_year,
@@ -61,11 +80,158 @@ pub(crate) fn aoc_puzzle_impl(args: TokenStream, input: TokenStream) -> TokenStr
 
             solutions.register_solution(Box::new(wrapper));
         }
+
+        #example_tests
+
+        #refresh_trigger
     };
 
     expanded
 }
 
+/// The puzzle description for the docs, and a file to include so the macro expands again when it changes
+///
+/// With `fetch_in_build = false` in `aoc.toml` only the cache is read, the doc generation step downloads the text.
+fn puzzle_description(
+    config: &AocConfig,
+    aoc_day: u32,
+    aoc_year: u32,
+) -> (String, Option<PathBuf>) {
+    if config.fetch_in_build.unwrap_or(true) {
+        // Download the text when it is missing, or once part 1 is solved for the part 2 description
+        let updated = update_puzzle_text(aoc_day, aoc_year);
+        let mut description = match (cached_puzzle_text(aoc_day, aoc_year), updated) {
+            (Ok(Some(description)), Ok(_)) => description,
+            (Ok(Some(description)), Err(e)) => format!(
+                "{}\n\nFailed to refresh the puzzle description: {}",
+                description, e
+            ),
+            (Ok(None), Ok(_)) => format!(
+                "Puzzle description for day {} ({}) is not cached",
+                aoc_day, aoc_year
+            ),
+            (Err(e), _) | (Ok(None), Err(e)) => format!(
+                "Failed to get puzzle description for day {} ({}): {}",
+                aoc_day, aoc_year, e
+            ),
+        };
+        // Expand again once part 1 is solved, so the docs pick up the part 2 description
+        let trigger = text_refresh_trigger(aoc_day, aoc_year).unwrap_or_else(|e| {
+            description.push_str(&format!(
+                "\n\nFailed to check for a part 2 description: {}",
+                e
+            ));
+            None
+        });
+        return (description, trigger);
+    }
+    let description = match cached_puzzle_text(aoc_day, aoc_year) {
+        Ok(Some(description)) => description,
+        Ok(None) => format!(
+            "Puzzle description for day {} ({}) is not cached, download it with `--fetch-docs`",
+            aoc_day, aoc_year
+        ),
+        Err(e) => format!(
+            "Failed to read puzzle description for day {} ({}): {}",
+            aoc_day, aoc_year, e
+        ),
+    };
+    // Expand again when the doc generation step updates the cache
+    (description, cached_text_trigger(aoc_day, aoc_year))
+}
+
+/// Include a file in the expanded code, so the macro expands again when it changes
+///
+/// The path is relative to the crate when possible, so the expanded code does not depend on where it is built.
+fn include_file(path: &Path) -> TokenStream {
+    let Some(manifest_dir) = env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from) else {
+        let path = path.to_string_lossy();
+        return quote! { const _: &[u8] = include_bytes!(#path); };
+    };
+    let relative = format!("/{}", relative_path(&manifest_dir, path).to_string_lossy());
+    quote! { const _: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), #relative)); }
+}
+
+/// Path of `path` relative to the `base` directory, going up with `..` where needed
+fn relative_path(base: &Path, path: &Path) -> PathBuf {
+    let Some(common) = base.ancestors().find(|dir| path.starts_with(dir)) else {
+        return path.to_path_buf();
+    };
+    let mut relative = PathBuf::new();
+    for _ in base.strip_prefix(common).unwrap().components() {
+        relative.push("..");
+    }
+    relative.join(path.strip_prefix(common).unwrap())
+}
+
+/// Generate a test module asserting the expected answers of each example
+///
+/// A combined solution gets one test per example, solving both parts once.
+fn example_tests(
+    ident: &syn::Ident,
+    combined: bool,
+    examples: &[ExampleAttributes],
+    aoc_day: u32,
+    aoc_year: u32,
+) -> TokenStream {
+    if examples.is_empty() {
+        return TokenStream::new();
+    }
+    let tests = examples.iter().enumerate().flat_map(|(i, example)| {
+        let puzzle = match &example.input {
+            Some(input) => quote! { aoc_core::Puzzle::from(#input) },
+            None => {
+                let n = example.n.unwrap_or(1);
+                quote! { aoc_core::Puzzle::example(#aoc_day, #aoc_year, #n) }
+            }
+        };
+        let parts = [("part1", &example.part1), ("part2", &example.part2)]
+            .into_iter()
+            .filter_map(|(part, expected)| expected.as_ref().map(|e| (part, e)));
+        if combined {
+            let test_name = quote::format_ident!("example{}", i + 1);
+            let asserts = parts.map(|(part, expected)| {
+                let index = syn::Index::from(if part == "part1" { 0 } else { 1 });
+                quote! {
+                    let expected: aoc_core::Answer = (#expected).into();
+                    assert_eq!(answers.#index, expected, #part);
+                }
+            });
+            return vec![quote! {
+                #[test]
+                fn #test_name() {
+                    let puzzle = #puzzle;
+                    let answers = aoc_core::CombinedPuzzleSolution::run(&<#ident as ::core::default::Default>::default(), &puzzle);
+                    #(#asserts)*
+                }
+            }];
+        }
+        parts
+            .map(|(part, expected)| {
+                let test_name = quote::format_ident!("example{}_{}", i + 1, part);
+                let run_part = quote::format_ident!("run_{}", part);
+                quote! {
+                    #[test]
+                    fn #test_name() {
+                        let puzzle = #puzzle;
+                        let result = aoc_core::ParsedPuzzleSolution::#run_part(&<#ident as ::core::default::Default>::default(), &puzzle);
+                        let expected: aoc_core::Answer = (#expected).into();
+                        assert_eq!(result, expected);
+                    }
+                }
+            })
+            .collect()
+    });
+    quote! {
+        #[cfg(test)]
+        mod aoc_examples {
+            use super::*;
+
+            #(#tests)*
+        }
+    }
+}
+
 fn extract_day_year(
     input: &syn::ItemStruct,
     day_from_arg: Option<u32>,
@@ -101,7 +267,7 @@ fn get_aoc_year() -> Result<u32, darling::Error> {
     match get_year() {
         Some(year) => Ok(year),
         _ => Err(darling::Error::custom(
-            "AOC year not set, call `aoc_core::set_year()`".to_string(),
+            "AOC year not set, use `#[aoc(year = 2025)]` or set `year` in aoc.toml".to_string(),
         )),
     }
 }
@@ -4,16 +4,43 @@ use darling::FromMeta;
 #[darling(and_then = AocAttributes::autocorrect)]
 pub struct AocAttributes {
     pub day: Option<u32>,
+    /// Document the struct with the puzzle description, `doc = false` leaves the puzzle text alone
+    pub doc: Option<bool>,
+    /// The struct implements `CombinedPuzzleSolution`, solving both parts at once
+    #[darling(default)]
+    pub combined: bool,
+    #[darling(multiple, rename = "example")]
+    pub examples: Vec<ExampleAttributes>,
 }
 
 impl AocAttributes {
     fn autocorrect(self) -> darling::Result<Self> {
         Ok(Self {
             day: validate_day(self.day)?,
+            doc: self.doc,
+            combined: self.combined,
+            examples: self.examples,
         })
     }
 }
 
+/// Expected answers for an example, e.g. `example(part1 = 3, part2 = 6)`
+#[derive(Debug, FromMeta)]
+pub struct ExampleAttributes {
+    /// Example number in the puzzle text, defaults to 1
+    pub n: Option<usize>,
+    /// Input to use instead of the example from the puzzle text
+    pub input: Option<String>,
+    #[darling(default, with = parse_answer)]
+    pub part1: Option<syn::Expr>,
+    #[darling(default, with = parse_answer)]
+    pub part2: Option<syn::Expr>,
+}
+
+fn parse_answer(meta: &syn::Meta) -> darling::Result<Option<syn::Expr>> {
+    darling::util::parse_expr::preserve_str_literal(meta).map(Some)
+}
+
 pub fn validate_day(day: Option<u32>) -> Result<Option<u32>, darling::Error> {
     if day.is_some_and(|d| !(1..=25).contains(&d)) {
         Err(darling::Error::custom("day must be between 1 and 25"))
@@ -23,7 +23,31 @@ mod aoc_puzzle;
 /// }
 /// ```
 ///
-/// Before you can call this macro in your code, you need to set `#[aoc(year = 2025)] on your main func`.
+/// To parse the input once for both parts, implement `ParsedPuzzleSolution` instead, with a `Parsed` type and a
+/// `parse` method; the runner reports the parse time separately.
+///
+/// When part 2 reuses intermediate results of part 1, implement `CombinedPuzzleSolution` instead, with a `parse`
+/// method and a `solve` method returning both answers, and mark the struct as combined:
+/// ```no_compile
+/// #[aoc_puzzle(day = 1, combined)]
+/// ```
+///
+/// Tests for the examples in the puzzle text can be generated by listing the expected answers:
+/// ```no_compile
+/// #[aoc_puzzle(day = 1, example(part1 = 3, part2 = 6), example(n = 2, part2 = 10))]
+/// ```
+/// Each `example` loads the n-th example (default 1) from the puzzle text, or uses `input = "..."` when set.
+///
+/// The struct is documented with the puzzle description. Set `doc = false` to leave it out, so expanding the macro
+/// never reads or downloads the puzzle text:
+/// ```no_compile
+/// #[aoc_puzzle(day = 1, doc = false)]
+/// ```
+/// With `fetch_in_build = false` in `aoc.toml` the description is only read from the cache, and downloaded by a
+/// separate doc generation step instead of during the build.
+///
+/// Before you can call this macro in your code, you need to set `#[aoc(year = 2025)] on your main func`, or set
+/// `year` in `aoc.toml`.
 #[proc_macro_attribute]
 pub fn aoc_puzzle(args: TokenStream, input: TokenStream) -> TokenStream {
     aoc_puzzle::aoc_puzzle_impl(args.into(), input.into()).into()
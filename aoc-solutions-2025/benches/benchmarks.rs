@@ -12,21 +12,21 @@ fn benchmarks(c: &mut Criterion) {
 }
 
 fn bench_day(c: &mut Criterion, day: u32, collection: &SolutionCollection) {
+    let puzzle = match collection.get_puzzle(&day) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!("Skipping day {}, input unavailable: {}", day, e);
+            return;
+        }
+    };
+    let functions = collection.prepare_bench(&day, &puzzle);
     let mut group = c.benchmark_group(format!("day{}", day).as_str());
     // group.sample_size(20);
     group.measurement_time(Duration::from_secs(20));
     group.sampling_mode(criterion::SamplingMode::Flat);
-    let (part1, part2) = collection.prepare_bench(&day);
-    group.bench_function("part1", |b| {
-        b.iter(|| {
-            part1();
-        })
-    });
-    group.bench_function("part2", |b| {
-        b.iter(|| {
-            part2();
-        })
-    });
+    for (name, function) in functions {
+        group.bench_function(name, |b| b.iter(&function));
+    }
     group.finish();
 }
 
@@ -0,0 +1,153 @@
+use aoc_core::{format_countdown, puzzle_days, Leaderboard, LeaderboardMember};
+
+/// Fetch a private leaderboard and print it, with the completion times of a single day when `day` is set
+pub fn show_leaderboard(id: u64, year: u32, day: Option<u32>) -> Result<(), aoc_core::AocError> {
+    let leaderboard = aoc_core::get_leaderboard(id, year)?;
+    match day {
+        Some(day) => print!("{}", render_day(&leaderboard, day, year)),
+        None => print!("{}", render_overview(&leaderboard, year)),
+    }
+    Ok(())
+}
+
+/// Members ranked by local score, with their stars per day: `*` for both parts, `+` for part 1 only
+fn render_overview(leaderboard: &Leaderboard, year: u32) -> String {
+    let days = puzzle_days(year);
+    let rank_width = leaderboard.members.len().to_string().len() + 1;
+    let padding = " ".repeat(rank_width + 14);
+    let tens = (1..=days)
+        .map(|day| match day / 10 {
+            0 => ' ',
+            tens => char::from_digit(tens % 10, 10).unwrap(),
+        })
+        .collect::<String>();
+    let units = (1..=days)
+        .map(|day| char::from_digit(day % 10, 10).unwrap())
+        .collect::<String>();
+
+    let mut output = format!(
+        "Private leaderboard {} ({} members)\n\n",
+        leaderboard.event,
+        leaderboard.members.len()
+    );
+    output.push_str(&format!("{}{}\n", padding, tens.trim_end()));
+    output.push_str(&format!(
+        "{:>rank_width$} {:>5} {:>5}  {}\n",
+        "",
+        "Score",
+        "Stars",
+        units,
+        rank_width = rank_width
+    ));
+    for (rank, member) in (1..).zip(leaderboard.ranking()) {
+        output.push_str(&format!(
+            "{:>rank_width$} {:>5} {:>5}  {}  {}\n",
+            format!("{})", rank),
+            member.local_score,
+            member.stars,
+            day_stars(member, days),
+            member.display_name(),
+            rank_width = rank_width
+        ));
+    }
+    output.push_str("\n* both parts, + part 1 only\n");
+    output
+}
+
+fn day_stars(member: &LeaderboardMember, days: u32) -> String {
+    (1..=days)
+        .map(|day| match member.day_stars(day) {
+            0 => '.',
+            1 => '+',
+            _ => '*',
+        })
+        .collect()
+}
+
+/// Time each member took to collect the stars of a day, fastest part 2 first
+fn render_day(leaderboard: &Leaderboard, day: u32, year: u32) -> String {
+    let mut members = leaderboard
+        .members
+        .values()
+        .filter(|member| member.day_stars(day) > 0)
+        .map(|member| {
+            (
+                member.completion_time(day, 1, year),
+                member.completion_time(day, 2, year),
+                member,
+            )
+        })
+        .collect::<Vec<_>>();
+    members.sort_by_key(|(part1, part2, member)| (part2.is_none(), *part2, *part1, member.id));
+
+    let mut output = format!("Day {} of {}\n\n", day, year);
+    if members.is_empty() {
+        output.push_str("No stars collected yet\n");
+        return output;
+    }
+    let rank_width = members.len().to_string().len() + 1;
+    output.push_str(&format!(
+        "{:>rank_width$} {:<14} {:<14} Name\n",
+        "",
+        "Part 1",
+        "Part 2",
+        rank_width = rank_width
+    ));
+    let format_time =
+        |time: Option<std::time::Duration>| time.map_or("-".to_string(), format_countdown);
+    for (rank, (part1, part2, member)) in (1..).zip(members) {
+        output.push_str(&format!(
+            "{:>rank_width$} {:<14} {:<14} {}\n",
+            format!("{})", rank),
+            format_time(part1),
+            format_time(part2),
+            member.display_name(),
+            rank_width = rank_width
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Day 1 of 2025 released at 1764565200
+    const LEADERBOARD: &str = include_str!("../test_data/leaderboard/private.json");
+
+    #[test]
+    fn test_render_overview() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        assert_eq!(
+            render_overview(&leaderboard, 2025),
+            "Private leaderboard 2025 (3 members)
+
+                         111
+   Score Stars  123456789012
+1)     7     3  *+..........  Alice
+2)     3     2  *...........  (anonymous user #2)
+3)     0     0  ............  Carol
+
+* both parts, + part 1 only
+"
+        );
+    }
+
+    #[test]
+    fn test_render_day() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        assert_eq!(
+            render_day(&leaderboard, 1, 2025),
+            "Day 1 of 2025
+
+   Part 1         Part 2         Name
+1) 5m 0s          20m 0s         Alice
+2) 13m 20s        1h 20m 0s      (anonymous user #2)
+"
+        );
+        assert_eq!(
+            render_day(&leaderboard, 3, 2025),
+            "Day 3 of 2025\n\nNo stars collected yet\n"
+        );
+    }
+}
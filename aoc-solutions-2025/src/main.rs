@@ -1,26 +1,281 @@
 use clap::Parser;
 
+mod leaderboard;
+mod reader;
+mod scaffold;
 mod solutions;
 
+const YEAR: u32 = 2025;
+
 #[derive(Parser, Debug)]
 #[command(name = "AOC 2025")]
 #[command(author = "Daan Sieben")]
 #[command(version = "1.0")]
 #[command(about, long_about = None)]
 struct Args {
-    /// AOC Session id; if not set uses env var AOC_SESSION
+    /// AOC Session id; if not set uses env var AOC_SESSION, the session file in aoc.toml or ~/.config/aoc/session
     #[arg(long)]
     aoc_session: Option<String>,
+    /// Only use cached puzzle data, never connect to AOC; can also be enabled with env var AOC_OFFLINE
+    #[arg(long)]
+    offline: bool,
     /// Puzzle day to run
     #[arg(short, long)]
     day: Option<u32>,
+    /// Submit the answers of the puzzle day to AOC
+    #[arg(long, requires = "day")]
+    submit: bool,
+    /// Verify the answers of all days against the recorded correct answers
+    #[arg(long, conflicts_with_all = ["day", "submit"])]
+    verify: bool,
+    /// Download the correct answers missing from the answer ledgers from the puzzle pages before verifying
+    #[arg(long, requires = "verify", conflicts_with = "offline")]
+    fetch_answers: bool,
+    /// Check the cached puzzle data for error pages and modified files
+    #[arg(long, conflicts_with_all = ["day", "submit", "verify"])]
+    check_cache: bool,
+    /// Download the data of a puzzle day again, replacing the cached files
+    #[arg(long, value_name = "DAY", conflicts_with_all = ["day", "submit", "verify", "check_cache", "offline"])]
+    refetch: Option<u32>,
+    /// Check whether AOC accepts the session, and report the logged in user
+    #[arg(long, conflicts_with_all = ["day", "submit", "verify", "offline"])]
+    check_session: bool,
+    /// Encrypt the cached input and text of all days, using the passphrase in env var AOC_CACHE_KEY
+    #[arg(long, conflicts_with_all = ["day", "submit", "verify", "check_cache", "refetch"])]
+    encrypt_cache: bool,
+    /// Wait until a puzzle day unlocks, download its input and text, and create its solution module
+    #[arg(long, value_name = "DAY", conflicts_with_all = ["day", "submit", "verify", "check_cache", "refetch", "encrypt_cache", "offline"])]
+    wait_and_fetch: Option<u32>,
+    /// Download the input and text of every released day which is not cached yet
+    #[arg(long, group = "prefetch", conflicts_with_all = ["day", "submit", "verify", "check_cache", "refetch", "encrypt_cache", "wait_and_fetch", "offline"])]
+    fetch_all: bool,
+    /// Download the puzzle texts of all released days for the docs, when aoc.toml sets `fetch_in_build = false`
+    #[arg(long, group = "prefetch", conflicts_with_all = ["day", "submit", "verify", "check_cache", "refetch", "encrypt_cache", "wait_and_fetch", "offline"])]
+    fetch_docs: bool,
+    /// Year to download with --fetch-all or --fetch-docs
+    #[arg(long, requires = "prefetch", default_value_t = YEAR)]
+    year: u32,
+    /// Show the cached puzzle text of the day in the terminal
+    #[arg(long, requires = "day", conflicts_with_all = ["submit", "verify", "check_cache", "refetch", "encrypt_cache", "wait_and_fetch", "prefetch"])]
+    show: bool,
+    /// Only show one part of the puzzle text
+    #[arg(long, requires = "show", value_parser = clap::value_parser!(u32).range(1..=2))]
+    part: Option<u32>,
+    /// Show a private leaderboard, by default the one in aoc.toml; with --day the completion times of that day
+    #[arg(long, value_name = "ID", num_args = 0..=1, conflicts_with_all = ["submit", "verify", "check_cache", "refetch", "encrypt_cache", "wait_and_fetch", "show"])]
+    leaderboard: Option<Option<u64>>,
 }
 
 #[aoc_core::aoc(year = 2025)]
 fn main() {
     let args = Args::parse();
+    // Report an invalid aoc.toml up front, instead of when puzzle data is first needed
+    let config = match aoc_core::get_config() {
+        Ok(config) => config,
+        Err(e) => {
+            println!("{}", e);
+            std::process::exit(1);
+        }
+    };
     if let Some(session_id) = args.aoc_session {
         aoc_core::set_session(session_id);
     }
-    solutions::run(args.day);
+    if args.offline {
+        aoc_core::set_offline(true);
+    }
+    if args.check_session {
+        match aoc_core::check_session() {
+            Ok(info) => {
+                println!("Logged in as {}", info.user);
+                if let Some(expires) = info.expires {
+                    println!("Session expires: {}", expires);
+                }
+            }
+            Err(e) => {
+                println!("Session check failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    if args.check_cache {
+        if !solutions::get_collection().check_cache() {
+            std::process::exit(1);
+        }
+        return;
+    }
+    if let Some(day) = args.refetch {
+        if let Err(e) = solutions::get_collection().refetch(&day) {
+            println!("Day {} refetch failed: {}", day, e);
+            std::process::exit(1);
+        }
+        println!("Day {} refetched", day);
+        return;
+    }
+    if args.encrypt_cache {
+        if let Err(e) = solutions::get_collection().encrypt_cache() {
+            println!("Encrypting cache failed: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if let Some(day) = args.wait_and_fetch {
+        if let Err(e) = wait_and_fetch(day) {
+            println!("Day {} fetch failed: {}", day, e);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if args.fetch_all {
+        if !fetch_all(args.year) {
+            std::process::exit(1);
+        }
+        return;
+    }
+    if args.fetch_docs {
+        if !fetch_docs(args.year) {
+            std::process::exit(1);
+        }
+        return;
+    }
+    if args.show {
+        let day = args.day.expect("--show requires --day");
+        if let Err(e) = reader::show_puzzle(day, YEAR, args.part) {
+            println!("Day {} puzzle text unavailable: {}", day, e);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if let Some(id) = args.leaderboard {
+        let Some(id) = id.or(config.leaderboard) else {
+            println!("No leaderboard id, pass --leaderboard ID or set leaderboard in aoc.toml");
+            std::process::exit(1);
+        };
+        if let Err(e) = leaderboard::show_leaderboard(id, YEAR, args.day) {
+            println!("Leaderboard {} unavailable: {}", id, e);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if args.verify {
+        if !solutions::verify(args.fetch_answers) {
+            std::process::exit(1);
+        }
+        return;
+    }
+    solutions::run_with_options(
+        args.day,
+        aoc_core::RunOptions {
+            submit: args.submit,
+        },
+    );
+}
+
+fn wait_and_fetch(day: u32) -> Result<(), aoc_core::AocError> {
+    if let Some(remaining) = aoc_core::time_until_release(day, YEAR)? {
+        println!(
+            "Day {} unlocks in {}, waiting",
+            day,
+            aoc_core::format_countdown(remaining)
+        );
+        aoc_core::wait_for_release(day, YEAR)?;
+    }
+    aoc_core::get_aoc_data(aoc_core::AocDataType::Input, day, YEAR)?;
+    aoc_core::get_aoc_data(aoc_core::AocDataType::Text, day, YEAR)?;
+    println!("Day {} downloaded", day);
+    match scaffold::scaffold_day(day)? {
+        Some(path) => println!("Created {}", path.display()),
+        None => println!("Day {} solution already exists", day),
+    }
+    Ok(())
+}
+
+/// Download the missing input and text of all released days; returns whether all downloads succeeded
+fn fetch_all(year: u32) -> bool {
+    for_released_days(year, |day| {
+        [
+            (aoc_core::AocDataType::Input, "input"),
+            (aoc_core::AocDataType::Text, "text"),
+        ]
+        .into_iter()
+        .filter(|(data_type, _)| !aoc_core::is_cached(data_type, day, year))
+        .map(|(data_type, name)| -> DayTask {
+            (
+                name,
+                Box::new(move || {
+                    aoc_core::get_aoc_data(data_type, day, year).map(|_| "downloaded")
+                }),
+            )
+        })
+        .collect()
+    })
+}
+
+/// Doc generation step: download the puzzle texts `#[aoc_puzzle]` reads from the cache, including part 2
+/// descriptions unlocked since they were cached
+fn fetch_docs(year: u32) -> bool {
+    for_released_days(year, |day| {
+        let task: DayTask = (
+            "text",
+            Box::new(move || {
+                aoc_core::update_puzzle_text(day, year).map(|downloaded| {
+                    if downloaded {
+                        "downloaded"
+                    } else {
+                        "up to date"
+                    }
+                })
+            }),
+        );
+        vec![task]
+    })
+}
+
+/// Named download of a day, reporting what it did
+type DayTask = (
+    &'static str,
+    Box<dyn FnOnce() -> Result<&'static str, aoc_core::AocError>>,
+);
+
+/// Run the downloads of all released days one at a time, with a progress report
+///
+/// Requests are throttled by the fetcher. Stops at the first error which would fail the other days as well.
+fn for_released_days(year: u32, tasks: impl Fn(u32) -> Vec<DayTask>) -> bool {
+    let days = (1..=aoc_core::puzzle_days(year))
+        .filter(|day| matches!(aoc_core::time_until_release(*day, year), Ok(None)))
+        .collect::<Vec<_>>();
+    if days.is_empty() {
+        println!("No puzzles of {} are released yet", year);
+        return true;
+    }
+    let mut failed = 0;
+    for (i, &day) in days.iter().enumerate() {
+        let progress = format!("[{}/{}] Day {}", i + 1, days.len(), day);
+        let tasks = tasks(day);
+        if tasks.is_empty() {
+            println!("{}: cached", progress);
+        }
+        for (name, task) in tasks {
+            match task() {
+                Ok(outcome) => println!("{}: {} {}", progress, name, outcome),
+                Err(e) => {
+                    println!("{}: {} failed: {}", progress, name, e);
+                    failed += 1;
+                    if matches!(
+                        e,
+                        aoc_core::AocError::MissingSession
+                            | aoc_core::AocError::SessionExpired
+                            | aoc_core::AocError::RateLimited { .. }
+                    ) {
+                        println!("Stopped, the remaining days would fail as well");
+                        return false;
+                    }
+                }
+            }
+        }
+    }
+    if failed > 0 {
+        println!("{} downloads failed, run again to retry them", failed);
+    }
+    failed == 0
 }
@@ -0,0 +1,337 @@
+use std::io::IsTerminal;
+
+const PART_TWO_HEADING: &str = "## --- Part Two ---";
+const RULE_WIDTH: usize = 60;
+
+/// Print the cached puzzle text of a day, styled for the terminal when stdout is one
+///
+/// Only the given part is printed when `part` is set. Colors are disabled with the `NO_COLOR` environment variable.
+/// The text is never downloaded, reading a puzzle does not count as a request to AOC.
+pub fn show_puzzle(day: u32, year: u32, part: Option<u32>) -> Result<(), aoc_core::AocError> {
+    let Some(text) = aoc_core::cached_puzzle_text(day, year)? else {
+        return Err(aoc_core::AocError::NotFound(
+            "the puzzle text is not cached, download it with --fetch-all".to_string(),
+        ));
+    };
+    let Some(text) = select_part(&text, part) else {
+        println!("Day {} part {} is not unlocked yet", day, part.unwrap_or(2));
+        return Ok(());
+    };
+    let styled = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    print!("{}", render(&text, styled));
+    Ok(())
+}
+
+/// The Markdown of a single part of the puzzle text, with the footnotes it refers to
+///
+/// Returns `None` when part 2 is requested but not in the text yet.
+fn select_part(markdown: &str, part: Option<u32>) -> Option<String> {
+    let (body, footnotes): (Vec<&str>, Vec<&str>) =
+        markdown.lines().partition(|line| !line.starts_with("[^"));
+    let body = body.join("\n");
+    let part_two = body.find(PART_TWO_HEADING);
+    let body = match (part, part_two) {
+        (None, _) | (Some(1), None) => body.as_str(),
+        (Some(1), Some(start)) => &body[..start],
+        (_, Some(start)) => &body[start..],
+        (_, None) => return None,
+    };
+    let mut text = body.trim_end().to_string();
+    text.push('\n');
+    let footnotes = footnotes
+        .into_iter()
+        .filter(|footnote| {
+            let reference = footnote.split(':').next().unwrap_or_default();
+            body.contains(reference)
+        })
+        .collect::<Vec<_>>();
+    if !footnotes.is_empty() {
+        text.push('\n');
+        text.push_str(&footnotes.join("\n"));
+        text.push('\n');
+    }
+    Some(text)
+}
+
+/// Render the Markdown written by the puzzle text converter as terminal text
+///
+/// Without styling the Markdown syntax is still removed, so the output reads as plain text.
+fn render(markdown: &str, styled: bool) -> String {
+    let style = |codes: &str, text: &str| {
+        if styled {
+            format!("\x1b[{}m{}\x1b[0m", codes, text)
+        } else {
+            text.to_string()
+        }
+    };
+    let mut output = String::new();
+    let mut code_fence: Option<&str> = None;
+    let mut html_code = false;
+    for line in markdown.lines() {
+        // Code blocks with highlights are HTML blocks
+        if html_code || (code_fence.is_none() && line.starts_with("<pre><code>")) {
+            let code = line.strip_prefix("<pre><code>").unwrap_or(line);
+            let end = code.strip_suffix("</code></pre>");
+            html_code = end.is_none();
+            let code = end.unwrap_or(code);
+            if !(code.is_empty() && end.is_some()) {
+                output.push_str(&format!("    {}\n", render_html_code(code, styled)));
+            }
+            continue;
+        }
+        let is_fence = line.len() >= 3 && line.chars().all(|c| c == '`');
+        match code_fence {
+            Some(fence) if line == fence => code_fence = None,
+            Some(_) => output.push_str(&format!("    {}\n", style("36", line))),
+            None if is_fence => code_fence = Some(line),
+            None => {
+                if line == PART_TWO_HEADING {
+                    output.push_str(&style("2", &"─".repeat(RULE_WIDTH)));
+                    output.push_str("\n\n");
+                }
+                if let Some(heading) = line.strip_prefix("## ") {
+                    output.push_str(&style("1;32", &render_inline(heading, false)));
+                } else if line == "***" {
+                    output.push_str(&style("2", &"─".repeat(RULE_WIDTH)));
+                } else {
+                    let content = line.trim_start();
+                    let indent = &line[..line.len() - content.len()];
+                    output.push_str(indent);
+                    match content.strip_prefix("- ") {
+                        Some(item) => {
+                            output.push_str("• ");
+                            output.push_str(&render_inline(item, styled));
+                        }
+                        None => output.push_str(&render_inline(content, styled)),
+                    }
+                }
+                output.push('\n');
+            }
+        }
+    }
+    output
+}
+
+/// Render a line of an HTML code block, with its `<em>` highlights
+fn render_html_code(line: &str, styled: bool) -> String {
+    let mut output = String::new();
+    for (i, segment) in line.split("<em>").enumerate() {
+        let (highlighted, plain) = match segment.split_once("</em>") {
+            Some((highlighted, plain)) if i > 0 => (highlighted, plain),
+            _ => ("", segment),
+        };
+        for (codes, text) in [("1;96", highlighted), ("36", plain)] {
+            let text = text
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&amp;", "&");
+            if styled && !text.is_empty() {
+                output.push_str(&format!("\x1b[{}m{}\x1b[0m", codes, text));
+            } else {
+                output.push_str(&text);
+            }
+        }
+    }
+    output
+}
+
+/// Render emphasis, code spans, links and footnote references of a line
+fn render_inline(text: &str, styled: bool) -> String {
+    let mut output = String::new();
+    let mut emphasis = false;
+    let mut link = false;
+    let set_style = |output: &mut String, emphasis: bool, code: bool, link: bool| {
+        if !styled {
+            return;
+        }
+        let mut codes = vec!["0"];
+        if emphasis {
+            codes.push("1");
+        }
+        if code {
+            codes.push(if emphasis { "96" } else { "36" });
+        } else if emphasis {
+            codes.push("97");
+        }
+        if link {
+            codes.push("4");
+        }
+        output.push_str(&format!("\x1b[{}m", codes.join(";")));
+    };
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '\\' if rest.len() > 1 => {
+                let escaped = rest[1..].chars().next().unwrap();
+                output.push(escaped);
+                rest = &rest[1 + escaped.len_utf8()..];
+                continue;
+            }
+            '`' => {
+                let fence_len = rest.len() - rest.trim_start_matches('`').len();
+                let after = &rest[fence_len..];
+                let fence = &rest[..fence_len];
+                if let Some(end) = after.find(fence) {
+                    let code = &after[..end];
+                    let code = match code.strip_prefix(' ').and_then(|c| c.strip_suffix(' ')) {
+                        Some(trimmed) if !trimmed.is_empty() => trimmed,
+                        _ => code,
+                    };
+                    set_style(&mut output, emphasis, true, link);
+                    output.push_str(code);
+                    set_style(&mut output, emphasis, false, link);
+                    rest = &after[end + fence_len..];
+                    continue;
+                }
+                output.push_str(fence);
+                rest = after;
+                continue;
+            }
+            '*' => {
+                let marker_len = if rest.starts_with("**") { 2 } else { 1 };
+                emphasis = !emphasis;
+                set_style(&mut output, emphasis, false, link);
+                rest = &rest[marker_len..];
+                continue;
+            }
+            '[' if rest.starts_with("[^") => {
+                if let Some(end) = rest.find(']') {
+                    let reference = format!("[{}]", &rest[2..end]);
+                    output.push_str(&if styled {
+                        format!("\x1b[2m{}\x1b[0m", reference)
+                    } else {
+                        reference
+                    });
+                    set_style(&mut output, emphasis, false, link);
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+            '[' => {
+                link = true;
+                set_style(&mut output, emphasis, false, link);
+                rest = &rest[1..];
+                continue;
+            }
+            ']' if link && rest.starts_with("](") => {
+                link = false;
+                set_style(&mut output, emphasis, false, link);
+                let end = link_end(rest).unwrap_or(rest.len() - 1);
+                output.push_str(&format!(" <{}>", &rest[2..end]));
+                rest = &rest[end + 1..];
+                continue;
+            }
+            _ => {}
+        }
+        output.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    if styled && (emphasis || link) {
+        output.push_str("\x1b[0m");
+    }
+    output
+}
+
+/// Position of the `)` closing the link destination in `](url)`, skipping balanced parentheses in the url
+fn link_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices().skip(2) {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(i),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "## --- Day 3: Test Tubes ---
+
+The *test tubes*[^1] have labels like `a-3`:
+
+```
+a-3
+b-14
+```
+
+<pre><code>c-<em>7</em>
+d-<em>10</em>
+</code></pre>
+
+- Tube `a-3` has checksum **`3`**.
+  - See the [input](https://adventofcode.com/2025/day/3/input).
+
+## --- Part Two ---
+
+Tubes like `2*3` are *upside down*[^2].
+
+[^1]: They are fragile.
+[^2]: Really.
+";
+
+    #[test]
+    fn test_select_part() {
+        assert_eq!(select_part(TEXT, None).unwrap(), TEXT);
+        let part_one = select_part(TEXT, Some(1)).unwrap();
+        assert!(part_one.ends_with("/input).\n\n[^1]: They are fragile.\n"));
+        assert!(!part_one.contains("Part Two"));
+        assert_eq!(
+            select_part(TEXT, Some(2)).unwrap(),
+            "## --- Part Two ---\n\nTubes like `2*3` are *upside down*[^2].\n\n[^2]: Really.\n"
+        );
+        let (unsolved, _) = TEXT.split_once(PART_TWO_HEADING).unwrap();
+        assert_eq!(select_part(unsolved, Some(2)), None);
+    }
+
+    #[test]
+    fn test_render_plain() {
+        assert_eq!(
+            render(&select_part(TEXT, Some(1)).unwrap(), false),
+            "--- Day 3: Test Tubes ---
+
+The test tubes[1] have labels like a-3:
+
+    a-3
+    b-14
+
+    c-7
+    d-10
+
+• Tube a-3 has checksum 3.
+  • See the input <https://adventofcode.com/2025/day/3/input>.
+
+[1]: They are fragile.
+"
+        );
+    }
+
+    #[test]
+    fn test_render_styled() {
+        let rendered = render("Tubes like `2*3` are *upside down*, **`3`**.\n", true);
+        assert_eq!(
+            rendered,
+            "Tubes like \x1b[0;36m2*3\x1b[0m are \x1b[0;1;97mupside down\x1b[0m, \x1b[0;1;97m\x1b[0;1;96m3\x1b[0;1;97m\x1b[0m.\n"
+        );
+        let rendered = render("<pre><code>a-<em>3</em>&lt;\n</code></pre>\n", true);
+        assert_eq!(
+            rendered,
+            "    \x1b[36ma-\x1b[0m\x1b[1;96m3\x1b[0m\x1b[36m<\x1b[0m\n"
+        );
+        let rendered = render(
+            "See [Set (mathematics)](https://en.wikipedia.org/wiki/Set_(mathematics)).\n",
+            false,
+        );
+        assert_eq!(
+            rendered,
+            "See Set (mathematics) <https://en.wikipedia.org/wiki/Set_(mathematics)>.\n"
+        );
+        let rendered = render(PART_TWO_HEADING, true);
+        assert!(rendered.starts_with("\x1b[2m──"));
+        assert!(rendered.ends_with("\x1b[1;32m--- Part Two ---\x1b[0m\n"));
+    }
+}
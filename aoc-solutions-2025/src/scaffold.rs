@@ -0,0 +1,67 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const SOLUTIONS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/solutions");
+
+lazy_static::lazy_static! {
+    static ref SETUP_SOLUTIONS_REGEX: regex::Regex = regex::Regex::new(r#"(?m)^setup_solutions!\(([^)]*)\);"#).unwrap();
+}
+
+/// Create the solution module of a day from the template, and register it in `solutions.rs`
+///
+/// Returns the path of the new module, or `None` when the day already exists.
+pub fn scaffold_day(day: u32) -> std::io::Result<Option<PathBuf>> {
+    scaffold_day_in(Path::new(SOLUTIONS_DIR), day)
+}
+
+fn scaffold_day_in(solutions_dir: &Path, day: u32) -> std::io::Result<Option<PathBuf>> {
+    let module = format!("day{:02}", day);
+    let path = solutions_dir.join(format!("{}.rs", module));
+    if path.exists() {
+        return Ok(None);
+    }
+    let template = fs::read_to_string(solutions_dir.join("template.rs"))?;
+    fs::write(
+        &path,
+        template.replace("day = _", &format!("day = {}", day)),
+    )?;
+
+    let solutions_file = solutions_dir.with_extension("rs");
+    let solutions = fs::read_to_string(&solutions_file)?;
+    fs::write(&solutions_file, add_solution_mod(&solutions, &module))?;
+    Ok(Some(path))
+}
+
+fn add_solution_mod(solutions: &str, module: &str) -> String {
+    SETUP_SOLUTIONS_REGEX
+        .replace(solutions, |captures: &regex::Captures| {
+            let mut modules = captures[1]
+                .split(',')
+                .map(str::trim)
+                .filter(|m| !m.is_empty())
+                .collect::<Vec<_>>();
+            if !modules.contains(&module) {
+                modules.push(module);
+            }
+            modules.sort();
+            format!("setup_solutions!({});", modules.join(", "))
+        })
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_solution_mod() {
+        let solutions = "use aoc_core::setup_solutions;\n\nsetup_solutions!(day01, day02);\n\n// setup_solutions!(\n//     day01, day02, day03\n// );\n";
+        assert_eq!(
+            add_solution_mod(solutions, "day03"),
+            "use aoc_core::setup_solutions;\n\nsetup_solutions!(day01, day02, day03);\n\n// setup_solutions!(\n//     day01, day02, day03\n// );\n"
+        );
+        assert_eq!(add_solution_mod(solutions, "day01"), solutions.to_string());
+    }
+}
@@ -1,23 +1,60 @@
-use std::ops::Range;
+use std::{num::ParseIntError, ops::Range, str::FromStr};
 
-use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{aoc_puzzle, Answer, ParseError, ParsedPuzzleSolution, Puzzle};
 
-#[aoc_puzzle(day = 1)]
+#[aoc_puzzle(
+    day = 1,
+    example(
+        input = r#"L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82
+"#,
+        part1 = 3,
+        part2 = 6
+    )
+)]
 #[derive(Default)]
 pub struct Day;
 
 const DIAL_RANGE: Range<i32> = 0..100;
 
-impl PuzzleSolution for Day {
-    fn part1(&self, puzzle: &Puzzle) -> Answer {
+/// Rotation of the dial, e.g. `L68`, negative to the left
+pub struct Rotation(i32);
+
+impl FromStr for Rotation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let direction = s.chars().next().ok_or("expected a rotation like L68")?;
+        let distance: i32 = s[direction.len_utf8()..]
+            .parse()
+            .map_err(|e: ParseIntError| e.to_string())?;
+        match direction {
+            'L' => Ok(Rotation(-distance)),
+            'R' => Ok(Rotation(distance)),
+            _ => Err(format!("invalid direction {direction}, expected L or R")),
+        }
+    }
+}
+
+impl ParsedPuzzleSolution for Day {
+    type Parsed<'a> = Vec<Rotation>;
+
+    fn parse<'a>(&self, puzzle: &'a Puzzle) -> Result<Self::Parsed<'a>, ParseError> {
+        puzzle.parse()
+    }
+
+    fn part1(&self, rotations: &Self::Parsed<'_>) -> Answer {
         let mut pos = 50;
         let mut zero_count = 0;
-        puzzle.get_input_lines().iter().for_each(|line| {
-            let move_by = match line.split_at(1) {
-                ("L", v) => -(v.parse::<i32>().unwrap()),
-                ("R", v) => v.parse::<i32>().unwrap(),
-                _ => panic!("Invalid direction"),
-            };
+        rotations.iter().for_each(|&Rotation(move_by)| {
             pos = (pos + move_by).rem_euclid(DIAL_RANGE.end);
             if pos == 0 {
                 zero_count += 1;
@@ -26,15 +63,10 @@ impl PuzzleSolution for Day {
         zero_count.into()
     }
 
-    fn part2(&self, puzzle: &Puzzle) -> Answer {
+    fn part2(&self, rotations: &Self::Parsed<'_>) -> Answer {
         let mut pos = 50;
         let mut zero_count = 0;
-        puzzle.get_input_lines().iter().for_each(|line| {   
-            let move_by = match line.split_at(1) {
-                ("L", v) => -(v.parse::<i32>().unwrap()),
-                ("R", v) => v.parse::<i32>().unwrap(),
-                _ => panic!("Invalid direction"),
-            };
+        rotations.iter().for_each(|&Rotation(move_by)| {
             let start_pos = pos;
             let new_pos = start_pos + move_by;
             pos = new_pos.rem_euclid(DIAL_RANGE.end);
@@ -55,36 +87,3 @@ impl PuzzleSolution for Day {
         zero_count.into()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn get_puzzle() -> Puzzle {
-        Puzzle::from(
-            r#"L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82
-"#,
-        )
-    }
-
-    #[test]
-    fn part1() {
-        let result = Day::default().part1(&get_puzzle());
-        assert_eq!(result, 3.into());
-    }
-
-    #[test]
-    fn part2() {
-        let result = Day::default().part2(&get_puzzle());
-        assert_eq!(result, 6.into());
-    }
-}
@@ -1,13 +1,31 @@
 use std::fmt::Display;
 
-use aoc_core::{aoc_puzzle, tools::Grid, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{aoc_puzzle, tools::Grid, Answer, CombinedPuzzleSolution, ParseError, Puzzle};
 
-#[aoc_puzzle(day = 4)]
+#[aoc_puzzle(
+    day = 4,
+    combined,
+    example(
+        input = r#"..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+"#,
+        part1 = 13,
+        part2 = 43
+    )
+)]
 #[derive(Default)]
 pub struct Day;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
-enum Cell {
+pub enum Cell {
     Empty,
     Roll,
 }
@@ -32,27 +50,18 @@ impl From<char> for Cell {
     }
 }
 
-impl PuzzleSolution for Day {
-    fn part1(&self, puzzle: &Puzzle) -> Answer {
-        let grid: Grid<usize, Cell> = puzzle.to_string().into();
-        grid.iter()
-            .filter(|((x, y), cell)| {
-                if *cell != &Cell::Roll {
-                    return false;
-                }
-                let roll_count = grid.all_neighbors(**x, **y)
-                    .iter()
-                    .filter(|(nx, ny)| grid.get(*nx, *ny) == Some(&Cell::Roll))
-                    .count();
-                roll_count < 4
-            })
-            .count()
-            .into()
+impl CombinedPuzzleSolution for Day {
+    type Parsed = Grid<usize, Cell>;
+
+    fn parse(&self, puzzle: &Puzzle) -> Result<Self::Parsed, ParseError> {
+        Ok(puzzle.to_string().into())
     }
 
-    fn part2(&self, puzzle: &Puzzle) -> Answer {
-        let mut grid: Grid<usize, Cell> = puzzle.to_string().into();
+    /// Part 1 counts the rolls removed in the first round of part 2
+    fn solve(&self, grid: &Self::Parsed) -> (Answer, Answer) {
+        let mut grid = grid.clone();
         let mut can_remove = true;
+        let mut first_round = None;
         let mut removed = 0;
         while can_remove {
             can_remove = false;
@@ -72,37 +81,8 @@ impl PuzzleSolution for Day {
                         can_remove = true;
                     }
                 });
+            first_round.get_or_insert(removed);
         }
-        removed.into()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn get_puzzle() -> Puzzle {
-        Puzzle::from(r#"..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@."#)
-    }
-
-    #[test]
-    fn part1() {
-        let result = Day::default().part1(&get_puzzle());
-        assert_eq!(result, 13.into());
-    }
-
-    #[test]
-    fn part2() {
-        let result = Day::default().part2(&get_puzzle());
-        assert_eq!(result, 43.into());
+        (first_round.into(), removed.into())
     }
 }
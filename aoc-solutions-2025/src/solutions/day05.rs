@@ -1,37 +1,48 @@
-use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+use std::{num::ParseIntError, ops::RangeInclusive, str::FromStr};
+
+use aoc_core::{aoc_puzzle, Answer, ParseError, ParsedPuzzleSolution, Puzzle};
 
 #[aoc_puzzle(day = 5)]
 #[derive(Default)]
 pub struct Day;
 
-impl PuzzleSolution for Day {
-    fn part1(&self, puzzle: &Puzzle) -> Answer {
-        let (fresh_ranges, ingredients) = puzzle.input_as_str().split_once("\n\n").unwrap();
-        let fresh_ranges = fresh_ranges
-            .lines()
-            .map(|line| {
-                let (start, end) = line.split_once('-').unwrap();
-                (start.parse().unwrap(), end.parse().unwrap())
-            })
-            .map(|(start, end): (u64, u64)| { start..=end })
-            .collect::<Vec<_>>();
+/// Range of fresh ingredient ids, e.g. `3-5`
+pub struct FreshRange(RangeInclusive<u64>);
+
+impl FromStr for FreshRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('-').ok_or("expected a range like 3-5")?;
+        let start = start.parse().map_err(|e: ParseIntError| e.to_string())?;
+        let end = end.parse().map_err(|e: ParseIntError| e.to_string())?;
+        Ok(FreshRange(start..=end))
+    }
+}
+
+impl ParsedPuzzleSolution for Day {
+    type Parsed<'a> = (Vec<FreshRange>, Vec<u64>);
+
+    fn parse<'a>(&self, puzzle: &'a Puzzle) -> Result<Self::Parsed<'a>, ParseError> {
+        puzzle.parse()
+    }
+
+    fn part1(&self, (fresh_ranges, ingredients): &Self::Parsed<'_>) -> Answer {
         ingredients
-            .lines()
-            .map(|line| line.parse::<u64>().unwrap())
-            .filter(|ingredient| fresh_ranges.iter().any(|range| range.contains(ingredient)))
+            .iter()
+            .filter(|ingredient| {
+                fresh_ranges
+                    .iter()
+                    .any(|FreshRange(range)| range.contains(ingredient))
+            })
             .count()
             .into()
     }
 
-    fn part2(&self, puzzle: &Puzzle) -> Answer {
-        let (fresh_ranges, _) = puzzle.input_as_str().split_once("\n\n").unwrap();
+    fn part2(&self, (fresh_ranges, _): &Self::Parsed<'_>) -> Answer {
         let mut fresh_ranges = fresh_ranges
-            .lines()
-            .map(|line| {
-                let (start, end) = line.split_once('-').unwrap();
-                (start.parse().unwrap(), end.parse().unwrap())
-            })
-            .map(|(start, end): (u64, u64)| { start..=end })
+            .iter()
+            .map(|FreshRange(range)| range.clone())
             .collect::<Vec<_>>();
         fresh_ranges.sort_by_key(|range| *range.start());
         let mut total = 0;
@@ -75,13 +86,20 @@ mod tests {
 
     #[test]
     fn part1() {
-        let result = Day::default().part1(&get_puzzle());
+        let result = Day.run_part1(&get_puzzle());
         assert_eq!(result, 3.into());
     }
 
     #[test]
     fn part2() {
-        let result = Day::default().part2(&get_puzzle());
+        let result = Day.run_part2(&get_puzzle());
         assert_eq!(result, 14.into());
     }
+
+    #[test]
+    fn fresh_range() {
+        assert!(matches!("3-5".parse(), Ok(FreshRange(range)) if range == (3..=5)));
+        assert!("a3b-5c".parse::<FreshRange>().is_err());
+        assert!("3-99999999999999999999".parse::<FreshRange>().is_err());
+    }
 }
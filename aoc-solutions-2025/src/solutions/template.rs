@@ -1,5 +1,6 @@
 use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
 
+// Test the examples of the puzzle text by adding their answers, e.g. `example(part1 = 0, part2 = 0)`
 #[aoc_puzzle(day = _)]
 #[derive(Default)]
 pub struct Day;
@@ -13,24 +14,3 @@ impl PuzzleSolution for Day {
         0.into()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn get_puzzle() -> Puzzle {
-        Puzzle::from(r#""#)
-    }
-
-    #[test]
-    fn part1() {
-        let result = Day::default().part1(&get_puzzle());
-        assert_eq!(result, 0.into());
-    }
-
-    #[test]
-    fn part2() {
-        let result = Day::default().part2(&get_puzzle());
-        assert_eq!(result, 0.into());
-    }
-}
\ No newline at end of file